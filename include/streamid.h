@@ -0,0 +1,188 @@
+#ifndef STREAMID_H
+#define STREAMID_H
+
+/* Generated with cbindgen, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Opaque handle to a StreamID or CommitID, released with `streamid_free`.
+ */
+typedef struct StreamRef StreamRef;
+
+#define STREAMID_CODEC 206
+
+/**
+ * Error codes returned by the FFI functions, derived from `Error`.
+ */
+typedef enum StreamIdError {
+  STREAM_ID_ERROR_OK = 0,
+  STREAM_ID_ERROR_NULL_POINTER = 1,
+  STREAM_ID_ERROR_INVALID_UTF8 = 2,
+  STREAM_ID_ERROR_BUFFER_TOO_SMALL = 3,
+  STREAM_ID_ERROR_PARSE_URL = 10,
+  STREAM_ID_ERROR_PARSE_MULTIBASE = 11,
+  STREAM_ID_ERROR_PARSE_CODEC = 12,
+  STREAM_ID_ERROR_PARSE_STREAM_TYPE = 13,
+  STREAM_ID_ERROR_PARSE_GENESIS_CID = 14,
+  STREAM_ID_ERROR_PARSE_COMMIT_CID = 15,
+  STREAM_ID_ERROR_PARSE_TRAILING_DATA = 16,
+  STREAM_ID_ERROR_PARSE_KIND = 17,
+  STREAM_ID_ERROR_INVALID_STREAM_REF_IPLD = 20,
+  STREAM_ID_ERROR_INVALID_STREAM_TYPE_INDEX = 21,
+  STREAM_ID_ERROR_INVALID_STREAM_TYPE_NAME = 22,
+  STREAM_ID_ERROR_UNKNOWN_CID_VERSION = 23,
+  STREAM_ID_ERROR_CBOR_ENCODING = 24,
+  STREAM_ID_ERROR_CID = 25,
+  STREAM_ID_ERROR_IO = 26,
+  STREAM_ID_ERROR_MULTIHASH = 27,
+  STREAM_ID_ERROR_VARINT_DECODE = 28,
+} StreamIdError;
+
+/**
+ * Parse a `StreamRef` from a NUL-terminated string or URL into `*out`.
+ *
+ * # Safety
+ *
+ * `s` must be a valid NUL-terminated string and `out` valid for writes.
+ */
+enum StreamIdError streamid_from_str(const char *s, StreamRef **out);
+
+/**
+ * Parse a `StreamRef` from `len` bytes into `*out`.
+ *
+ * # Safety
+ *
+ * `bytes` must be valid for reads of `len` bytes and `out` valid for writes.
+ */
+enum StreamIdError streamid_from_bytes(const uint8_t *bytes, size_t len, StreamRef **out);
+
+/**
+ * Create a `CommitId` of the same stream at the commit CID string into `*out`, `"0"` for the
+ * genesis commit as in legacy URLs.
+ *
+ * # Safety
+ *
+ * `stream_ref` must be a live handle, `commit` a valid NUL-terminated string and `out` valid
+ * for writes.
+ */
+enum StreamIdError streamid_at_commit(const StreamRef *stream_ref,
+                                      const char *commit,
+                                      StreamRef **out);
+
+/**
+ * Release a handle, null is ignored.
+ *
+ * # Safety
+ *
+ * `stream_ref` must be null or a handle not yet released.
+ */
+void streamid_free(StreamRef *stream_ref);
+
+/**
+ * Get the stream type code.
+ *
+ * Returns `UINT64_MAX` when `stream_ref` is null.
+ *
+ * # Safety
+ *
+ * `stream_ref` must be null or a live handle.
+ */
+uint64_t streamid_stream_type(const StreamRef *stream_ref);
+
+/**
+ * Whether the handle is a `CommitId` rather than a `StreamId`.
+ *
+ * Returns false when `stream_ref` is null.
+ *
+ * # Safety
+ *
+ * `stream_ref` must be null or a live handle.
+ */
+bool streamid_is_commit_id(const StreamRef *stream_ref);
+
+/**
+ * Get the genesis CID string.
+ *
+ * Returns null when `stream_ref` is null.
+ *
+ * # Safety
+ *
+ * `stream_ref` must be null or a live handle.
+ */
+char *streamid_cid(const StreamRef *stream_ref);
+
+/**
+ * Get the commit CID string, the genesis CID for the genesis commit, null for a `StreamId`.
+ *
+ * Returns null when `stream_ref` is null.
+ *
+ * # Safety
+ *
+ * `stream_ref` must be null or a live handle.
+ */
+char *streamid_commit(const StreamRef *stream_ref);
+
+/**
+ * Encode into a base36 string.
+ *
+ * Returns null when `stream_ref` is null.
+ *
+ * # Safety
+ *
+ * `stream_ref` must be null or a live handle.
+ */
+char *streamid_to_base36(const StreamRef *stream_ref);
+
+/**
+ * Encode into a base36 URL.
+ *
+ * Returns null when `stream_ref` is null.
+ *
+ * # Safety
+ *
+ * `stream_ref` must be null or a live handle.
+ */
+char *streamid_to_url(const StreamRef *stream_ref);
+
+/**
+ * Get the length in bytes of the encoded handle.
+ *
+ * Returns 0 when `stream_ref` is null.
+ *
+ * # Safety
+ *
+ * `stream_ref` must be null or a live handle.
+ */
+size_t streamid_encoded_len(const StreamRef *stream_ref);
+
+/**
+ * Encode into `buf` of `buf_len` bytes, writing the encoded length to `*out_len`.
+ *
+ * Fails with `StreamIdError::BufferTooSmall` when `buf_len` is less than
+ * `streamid_encoded_len`, `*out_len` is still written.
+ *
+ * # Safety
+ *
+ * `stream_ref` must be a live handle, `buf` valid for writes of `buf_len` bytes and `out_len`
+ * valid for writes.
+ */
+enum StreamIdError streamid_to_bytes(const StreamRef *stream_ref,
+                                     uint8_t *buf,
+                                     size_t buf_len,
+                                     size_t *out_len);
+
+/**
+ * Release a string returned by this library, null is ignored.
+ *
+ * # Safety
+ *
+ * `s` must be null or a string returned by this library not yet released.
+ */
+void streamid_string_free(char *s);
+
+#endif /* STREAMID_H */
@@ -0,0 +1,130 @@
+//! Compile-time validated `StreamId` and `CommitId` literals, re-exported by the `streamid` crate
+//! with its `macros` feature.
+
+use std::io::Cursor;
+
+use cid::{
+    multibase::{decode, Base},
+    Cid,
+};
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Error, LitStr};
+
+const STREAMID_CODEC: u64 = 206;
+
+/// Create a `StreamId` from a base36 string literal validated at compile time.
+///
+/// Expands to a constant expression, so it can initialize `const` and `static` items.
+///
+/// ```rust,ignore
+/// use streamid::{stream_id, StreamId};
+///
+/// const STREAM_ID: StreamId = stream_id!("kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s");
+/// ```
+#[proc_macro]
+pub fn stream_id(input: TokenStream) -> TokenStream {
+    let lit = parse_macro_input!(input as LitStr);
+    expand(&lit, false)
+        .unwrap_or_else(|err| Error::new(lit.span(), err).to_compile_error())
+        .into()
+}
+
+/// Create a `CommitId` from a base36 string literal validated at compile time.
+///
+/// Expands to a constant expression, so it can initialize `const` and `static` items.
+///
+/// ```rust,ignore
+/// use streamid::{commit_id, CommitId};
+///
+/// const GENESIS: CommitId = commit_id!("k3y52l7qbv1frxwipl4hp7e6jlu4f6u8upm2xv0irmedfkm5cnutmezzi3u7mytj4");
+/// ```
+#[proc_macro]
+pub fn commit_id(input: TokenStream) -> TokenStream {
+    let lit = parse_macro_input!(input as LitStr);
+    expand(&lit, true)
+        .unwrap_or_else(|err| Error::new(lit.span(), err).to_compile_error())
+        .into()
+}
+
+fn expand(lit: &LitStr, commit_id: bool) -> Result<TokenStream2, String> {
+    let (base, bytes) = decode(lit.value()).map_err(|err| format!("invalid multibase: {err}"))?;
+    if base != Base::Base36Lower {
+        return Err(format!("expected a base36 string, found {base:?}"));
+    }
+
+    let (codec, bytes) = unsigned_varint::decode::u64(&bytes)
+        .map_err(|err| format!("invalid codec varint: {err}"))?;
+    if codec != STREAMID_CODEC {
+        return Err(format!(
+            "expected streamid codec {STREAMID_CODEC}, found {codec}"
+        ));
+    }
+
+    let (code, bytes) = unsigned_varint::decode::u64(bytes)
+        .map_err(|err| format!("invalid stream type varint: {err}"))?;
+    let stream_type = match code {
+        0 => quote!(Tile),
+        1 => quote!(Caip10Link),
+        2 => quote!(Model),
+        3 => quote!(Mid),
+        4 => quote!(Unloadable),
+        code => return Err(format!("unknown stream type {code}")),
+    };
+
+    let (cid, bytes) = read_cid(bytes).map_err(|err| format!("invalid genesis CID: {err}"))?;
+    let cid = cid_tokens(&cid);
+
+    if !commit_id {
+        if !bytes.is_empty() {
+            return Err("expected a StreamID, found a CommitID, use `commit_id!`".into());
+        }
+
+        return Ok(quote! {
+            ::streamid::StreamId {
+                stream_type: ::streamid::StreamType::#stream_type,
+                cid: #cid,
+            }
+        });
+    }
+
+    let commit = match bytes {
+        [] => return Err("expected a CommitID, found a StreamID, use `stream_id!`".into()),
+        // Genesis commit
+        [0] => quote!(::core::option::Option::None),
+        bytes => {
+            let (commit, bytes) =
+                read_cid(bytes).map_err(|err| format!("invalid commit CID: {err}"))?;
+            if !bytes.is_empty() {
+                return Err(format!("unexpected {} bytes after commit CID", bytes.len()));
+            }
+
+            let commit = cid_tokens(&commit);
+            quote!(::core::option::Option::Some(#commit))
+        }
+    };
+
+    Ok(quote! {
+        ::streamid::CommitId {
+            stream_type: ::streamid::StreamType::#stream_type,
+            cid: #cid,
+            commit: #commit,
+        }
+    })
+}
+
+fn read_cid(bytes: &[u8]) -> Result<(Cid, &[u8]), cid::Error> {
+    let mut r = Cursor::new(bytes);
+    let cid = Cid::read_bytes(&mut r)?;
+    Ok((cid, &bytes[r.position() as usize..]))
+}
+
+fn cid_tokens(cid: &Cid) -> TokenStream2 {
+    let version = u64::from(cid.version());
+    let codec = cid.codec();
+    let code = cid.hash().code();
+    let digest = cid.hash().digest();
+
+    quote!(::streamid::macros::cid(#version, #codec, #code, &[#(#digest),*]))
+}
@@ -0,0 +1,227 @@
+use std::{
+    error::Error,
+    fs,
+    io::{self, Cursor, Read},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    str::FromStr,
+};
+
+use clap::{Args, ValueEnum};
+use libipld::{cbor::DagCborCodec, json::DagJsonCodec, prelude::*};
+use serde_json::Value;
+use streamid::*;
+
+use crate::{inspect, parse};
+
+type CliResult<T> = Result<T, Box<dyn Error>>;
+
+/// Compute the StreamID of a genesis commit.
+///
+/// The genesis CID is the CID of the DAG-CBOR encoding of the commit, or the root CID of a CAR
+/// file, which also covers signed genesis commits.
+#[derive(Args)]
+pub struct GenesisArgs {
+    /// Genesis commit file, read from stdin when omitted or `-`.
+    file: Option<PathBuf>,
+
+    /// Stream type name or code.
+    #[arg(short = 't', long = "type", value_parser = parse_stream_type)]
+    stream_type: StreamType,
+
+    /// Input format, guessed from the file extension by default.
+    #[arg(short, long, value_enum)]
+    format: Option<Format>,
+
+    /// Fail unless the StreamID equals this StreamID, or the base ID of this CommitID.
+    #[arg(long)]
+    expect: Option<String>,
+
+    /// Print a JSON object.
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    /// DAG-JSON, with `{"/": ...}` links and bytes, the default.
+    DagJson,
+    /// Plain JSON, `{"/": ...}` is an ordinary map.
+    Json,
+    /// DAG-CBOR bytes, `.cbor` files.
+    DagCbor,
+    /// CARv1 with the genesis commit as its root, `.car` files.
+    Car,
+}
+
+pub fn genesis(args: GenesisArgs) -> ExitCode {
+    match run(args) {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: GenesisArgs) -> CliResult<ExitCode> {
+    let path = args.file.as_deref().filter(|path| *path != Path::new("-"));
+    let bytes = match path {
+        Some(path) => fs::read(path)?,
+        None => {
+            let mut bytes = Vec::new();
+            io::stdin().read_to_end(&mut bytes)?;
+            bytes
+        }
+    };
+
+    let stream_id = match args.format.unwrap_or_else(|| guess_format(path)) {
+        Format::DagJson => {
+            let genesis: Ipld = DagJsonCodec.decode(&bytes)?;
+            StreamId::from_genesis(args.stream_type, &genesis)?
+        }
+        Format::Json => {
+            let genesis = to_ipld(serde_json::from_slice(&bytes)?)?;
+            StreamId::from_genesis(args.stream_type, &genesis)?
+        }
+        Format::DagCbor => {
+            let genesis: Ipld = DagCborCodec.decode(&bytes)?;
+            if DagCborCodec.encode(&genesis)? != bytes {
+                eprintln!(
+                    "warning: input is not canonical DAG-CBOR, hashing its canonical encoding"
+                );
+            }
+            StreamId::from_genesis(args.stream_type, &genesis)?
+        }
+        Format::Car => {
+            let (root, block) = read_car(&bytes)?;
+            // Signed genesis commits are DAG-JOSE, only unsigned ones can be checked here.
+            if root.codec() == u64::from(DagCborCodec) {
+                let genesis: Ipld = DagCborCodec.decode(&block)?;
+                let canonical = StreamId::from_genesis(args.stream_type, &genesis)?;
+                if canonical.cid != root {
+                    eprintln!(
+                        "warning: root {root} is not the CID of its canonical DAG-CBOR encoding {}",
+                        canonical.cid
+                    );
+                }
+            }
+            StreamId {
+                stream_type: args.stream_type,
+                cid: root,
+            }
+        }
+    };
+
+    let expected = match &args.expect {
+        Some(expected) => Some(parse(expected)?.to_base_id()),
+        None => None,
+    };
+
+    let stream_ref = StreamRef::StreamId(stream_id.clone());
+    if args.json {
+        let mut json = inspect::to_json(&stream_ref);
+        if let Some(expected) = &expected {
+            json["expected"] = expected.to_string().into();
+            json["matches"] = (expected == &stream_id).into();
+        }
+        println!("{json}");
+    } else {
+        inspect::print_text(&stream_ref);
+    }
+
+    match expected {
+        Some(expected) if expected != stream_id => {
+            eprintln!("mismatch: expected {expected}");
+            if expected.stream_type != stream_id.stream_type {
+                eprintln!(
+                    "  stream type is {}, expected {}",
+                    stream_id.stream_type, expected.stream_type
+                );
+            }
+            if expected.cid != stream_id.cid {
+                eprintln!(
+                    "  genesis CID is {}, expected {}",
+                    stream_id.cid, expected.cid
+                );
+            }
+            Ok(ExitCode::FAILURE)
+        }
+        _ => Ok(ExitCode::SUCCESS),
+    }
+}
+
+fn parse_stream_type(s: &str) -> Result<StreamType> {
+    match u64::from_str(s) {
+        Ok(code) => Ok(StreamType::from_code(code)),
+        Err(_) => StreamType::from_str(s),
+    }
+}
+
+fn guess_format(path: Option<&Path>) -> Format {
+    match path.and_then(Path::extension).and_then(|ext| ext.to_str()) {
+        Some("car") => Format::Car,
+        Some("cbor" | "dagcbor") => Format::DagCbor,
+        _ => Format::DagJson,
+    }
+}
+
+fn to_ipld(value: Value) -> CliResult<Ipld> {
+    Ok(match value {
+        Value::Null => Ipld::Null,
+        Value::Bool(value) => Ipld::Bool(value),
+        Value::Number(value) => match (value.as_i64(), value.as_u64(), value.as_f64()) {
+            (Some(int), _, _) => Ipld::Integer(int.into()),
+            (_, Some(int), _) => Ipld::Integer(int.into()),
+            (_, _, Some(float)) if float.is_finite() => Ipld::Float(float),
+            _ => return Err(format!("number {value} has no IPLD representation").into()),
+        },
+        Value::String(value) => Ipld::String(value),
+        Value::Array(value) => {
+            Ipld::List(value.into_iter().map(to_ipld).collect::<CliResult<_>>()?)
+        }
+        Value::Object(value) => Ipld::Map(
+            value
+                .into_iter()
+                .map(|(key, value)| Ok((key, to_ipld(value)?)))
+                .collect::<CliResult<_>>()?,
+        ),
+    })
+}
+
+/// Read the single root CID of a CARv1 file and its block.
+fn read_car(bytes: &[u8]) -> CliResult<(Cid, Vec<u8>)> {
+    let (header, mut blocks) = read_section(bytes)?;
+    let root = match DagCborCodec.decode(header)? {
+        Ipld::Map(header) => match header.get("roots") {
+            Some(Ipld::List(roots)) => match roots.as_slice() {
+                [Ipld::Link(root)] => *root,
+                _ => return Err("CAR file must have exactly one root".into()),
+            },
+            _ => return Err("CAR header has no roots".into()),
+        },
+        _ => return Err("CAR header is not a map".into()),
+    };
+
+    while !blocks.is_empty() {
+        let (block, rest) = read_section(blocks)?;
+        blocks = rest;
+
+        let mut block = Cursor::new(block);
+        if Cid::read_bytes(&mut block)? == root {
+            let offset = block.position() as usize;
+            return Ok((root, block.into_inner()[offset..].to_vec()));
+        }
+    }
+
+    Err(format!("CAR file has no block for root {root}").into())
+}
+
+// Sections are prefixed with their varint length.
+fn read_section(bytes: &[u8]) -> CliResult<(&[u8], &[u8])> {
+    let (len, rest) = unsigned_varint::decode::usize(bytes)?;
+    if rest.len() < len {
+        return Err("CAR file is truncated".into());
+    }
+    Ok(rest.split_at(len))
+}
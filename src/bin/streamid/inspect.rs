@@ -0,0 +1,198 @@
+use std::process::ExitCode;
+
+use cid::multibase::Base;
+use clap::{Args, ValueEnum};
+use serde_json::{json, Value};
+use streamid::*;
+
+use crate::{for_each_input, parse, to_hex};
+
+/// Print the stream type, genesis and commit CIDs, base ID and URLs of each ID.
+///
+/// IDs are StreamID or CommitID strings in any supported multibase, `ceramic://` or
+/// `/ceramic/<id>?commit=<cid>` URLs, or hex bytes optionally prefixed with `0x`.
+#[derive(Args)]
+pub struct InspectArgs {
+    /// IDs to inspect, read one per line from stdin when none or `-` is given.
+    ids: Vec<String>,
+
+    /// Print one JSON object per line.
+    #[arg(long)]
+    json: bool,
+}
+
+/// Print each ID in another encoding.
+#[derive(Args)]
+pub struct ConvertArgs {
+    /// IDs to convert, read one per line from stdin when none or `-` is given.
+    ids: Vec<String>,
+
+    /// Encoding to print.
+    #[arg(long, value_enum, default_value = "base36")]
+    to: Encoding,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Encoding {
+    Base36,
+    Base32,
+    Base58btc,
+    Base64url,
+    Base16,
+    /// `ceramic://<id>`
+    Url,
+    /// `/ceramic/<stream id>?commit=<cid>`, `0` for the genesis commit.
+    LegacyUrl,
+    /// Bytes without multibase prefix.
+    Hex,
+}
+
+pub fn inspect(args: InspectArgs) -> ExitCode {
+    for_each_input(args.ids, |input| {
+        let stream_ref = parse(input)?;
+        if args.json {
+            println!("{}", to_json(&stream_ref));
+        } else {
+            print_text(&stream_ref);
+        }
+        Ok(())
+    })
+}
+
+pub fn convert(args: ConvertArgs) -> ExitCode {
+    for_each_input(args.ids, |input| {
+        let stream_ref = parse(input)?;
+        let converted = match args.to {
+            Encoding::Base36 => stream_ref.to_base36_string(),
+            Encoding::Base32 => stream_ref.to_string_of_base(Base::Base32Lower),
+            Encoding::Base58btc => stream_ref.to_string_of_base(Base::Base58Btc),
+            Encoding::Base64url => stream_ref.to_string_of_base(Base::Base64Url),
+            Encoding::Base16 => stream_ref.to_string_of_base(Base::Base16Lower),
+            Encoding::Url => stream_ref.to_url(),
+            Encoding::LegacyUrl => stream_ref.to_legacy_url(),
+            Encoding::Hex => to_hex(&stream_ref.to_bytes()),
+        };
+        println!("{converted}");
+        Ok(())
+    })
+}
+
+pub fn print_text(stream_ref: &StreamRef) {
+    println!("{stream_ref}");
+    println!("  kind:        {}", kind(stream_ref));
+    println!("  stream type: {}", stream_type(stream_ref.stream_type()));
+    print_cid("genesis CID:", stream_ref.cid());
+    if let StreamRef::CommitId(commit_id) = stream_ref {
+        match commit_id.commit {
+            Some(commit) => print_cid("commit CID: ", &commit),
+            None => println!("  commit CID:  genesis"),
+        }
+    }
+    println!("  base ID:     {}", stream_ref.to_base_id());
+    println!("  URL:         {}", stream_ref.to_url());
+    println!("  legacy URL:  {}", stream_ref.to_legacy_url());
+    println!("  hex:         {}", to_hex(&stream_ref.to_bytes()));
+    println!();
+}
+
+fn print_cid(label: &str, cid: &Cid) {
+    let hash = cid.hash();
+    println!("  {label} {cid}");
+    println!("    version:   {}", u64::from(cid.version()));
+    println!("    codec:     {}", code(cid.codec(), codec_name));
+    println!("    multihash: {}", code(hash.code(), multihash_name));
+    println!(
+        "    digest:    {} ({} bytes)",
+        to_hex(hash.digest()),
+        hash.size()
+    );
+}
+
+pub fn to_json(stream_ref: &StreamRef) -> Value {
+    let commit = match stream_ref {
+        StreamRef::StreamId(_) => Value::Null,
+        StreamRef::CommitId(CommitId { commit: None, .. }) => json!("genesis"),
+        StreamRef::CommitId(CommitId {
+            commit: Some(commit),
+            ..
+        }) => cid_json(commit),
+    };
+
+    json!({
+        "id": stream_ref.to_string(),
+        "kind": kind(stream_ref),
+        "stream_type": {
+            "code": stream_ref.stream_type().code(),
+            "name": stream_ref.stream_type().name(),
+        },
+        "genesis": cid_json(stream_ref.cid()),
+        "commit": commit,
+        "base_id": stream_ref.to_base_id().to_string(),
+        "url": stream_ref.to_url(),
+        "legacy_url": stream_ref.to_legacy_url(),
+        "hex": to_hex(&stream_ref.to_bytes()),
+    })
+}
+
+fn cid_json(cid: &Cid) -> Value {
+    let hash = cid.hash();
+    json!({
+        "cid": cid.to_string(),
+        "version": u64::from(cid.version()),
+        "codec": {
+            "code": cid.codec(),
+            "name": codec_name(cid.codec()),
+        },
+        "multihash": {
+            "code": hash.code(),
+            "name": multihash_name(hash.code()),
+            "size": hash.size(),
+            "digest": to_hex(hash.digest()),
+        },
+    })
+}
+
+fn kind(stream_ref: &StreamRef) -> &'static str {
+    match stream_ref {
+        StreamRef::StreamId(_) => "StreamID",
+        StreamRef::CommitId(_) => "CommitID",
+    }
+}
+
+fn stream_type(stream_type: StreamType) -> String {
+    match stream_type.name() {
+        Some(name) => format!("{name} ({})", stream_type.code()),
+        None => format!("unknown ({})", stream_type.code()),
+    }
+}
+
+fn code(code: u64, name: impl Fn(u64) -> Option<&'static str>) -> String {
+    match name(code) {
+        Some(name) => format!("{name} ({code:#x})"),
+        None => format!("unknown ({code:#x})"),
+    }
+}
+
+fn codec_name(code: u64) -> Option<&'static str> {
+    Some(match code {
+        0x55 => "raw",
+        0x70 => "dag-pb",
+        0x71 => "dag-cbor",
+        0x85 => "dag-jose",
+        0x0129 => "dag-json",
+        _ => return None,
+    })
+}
+
+fn multihash_name(code: u64) -> Option<&'static str> {
+    Some(match code {
+        0x00 => "identity",
+        0x12 => "sha2-256",
+        0x13 => "sha2-512",
+        0x16 => "sha3-256",
+        0x1b => "keccak-256",
+        0x1e => "blake3",
+        0xb220 => "blake2b-256",
+        _ => return None,
+    })
+}
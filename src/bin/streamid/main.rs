@@ -0,0 +1,100 @@
+//! `streamid` command-line tool, see `streamid --help`.
+
+mod genesis;
+mod inspect;
+
+use std::{
+    io::{self, BufRead},
+    process::ExitCode,
+};
+
+use clap::{Parser, Subcommand};
+use streamid::*;
+
+#[derive(Parser)]
+#[command(
+    version,
+    about = "Inspect, convert and compute Ceramic StreamIDs and CommitIDs"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    Inspect(inspect::InspectArgs),
+    Convert(inspect::ConvertArgs),
+    Genesis(genesis::GenesisArgs),
+}
+
+fn main() -> ExitCode {
+    match Cli::parse().command {
+        Command::Inspect(args) => inspect::inspect(args),
+        Command::Convert(args) => inspect::convert(args),
+        Command::Genesis(args) => genesis::genesis(args),
+    }
+}
+
+/// Run `f` on each ID given, or on each line of stdin when none or `-` is given.
+///
+/// Errors are reported on stderr with the failing input and do not stop the remaining inputs.
+fn for_each_input(ids: Vec<String>, mut f: impl FnMut(&str) -> Result<()>) -> ExitCode {
+    let inputs: Box<dyn Iterator<Item = io::Result<String>>> = match ids.as_slice() {
+        [] => Box::new(io::stdin().lock().lines()),
+        [id] if id == "-" => Box::new(io::stdin().lock().lines()),
+        _ => Box::new(ids.into_iter().map(Ok)),
+    };
+
+    let mut code = ExitCode::SUCCESS;
+    for input in inputs {
+        let input = match input {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("error: {err}");
+                return ExitCode::FAILURE;
+            }
+        };
+        let input = input.trim();
+        if input.is_empty() {
+            continue;
+        }
+
+        if let Err(err) = f(input) {
+            eprintln!("{input}: {err}");
+            code = ExitCode::FAILURE;
+        }
+    }
+
+    code
+}
+
+/// Parse a string, URL or hex bytes, keeping stream types unknown to this crate.
+fn parse(input: &str) -> Result<StreamRef> {
+    let options = ParseOptions {
+        allow_unknown_stream_types: true,
+        ..Default::default()
+    };
+
+    StreamRef::from_str_with(input, &options).or_else(|err| {
+        match from_hex(input.strip_prefix("0x").unwrap_or(input)) {
+            Some(bytes) => StreamRef::from_slice_with(bytes, &options),
+            None => Err(err),
+        }
+    })
+}
+
+fn from_hex(s: &str) -> Option<Vec<u8>> {
+    if s.len() % 2 != 0 {
+        return None;
+    }
+
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
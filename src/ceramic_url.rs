@@ -0,0 +1,234 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, str::FromStr};
+
+use cid::Cid;
+
+use crate::{util, *};
+
+const SCHEME: &str = "ceramic://";
+
+/// A `ceramic://<id>[/path][?query]` URL.
+///
+/// The `commit` query parameter, or `version` as it was formerly named, selects a commit of the
+/// stream like in `/ceramic/<id>?commit=<cid>` URLs, `0` being the genesis commit. It is folded
+/// into [`stream_ref`](CeramicUrl::stream_ref), the other parameters are kept in order as written,
+/// percent-encoding included.
+///
+/// URLs display in their canonical form: the base36 [`StreamRef`], which contains the commit,
+/// then the path and query. Parsing the displayed form of a parsed URL returns an equal
+/// [`CeramicUrl`], and canonical URLs display exactly as parsed. With the `url` feature it
+/// converts to and from `url::Url`, which keeps the path and query as written.
+///
+/// ```rust
+/// # use std::str::FromStr;
+/// #
+/// # use streamid::*;
+/// let url = CeramicUrl::from_str("ceramic://kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s/content?commit=bagjqcgzaday6dzalvmy5ady2m5a5legq5zrbsnlxfc2bfxej532ds7htpova&sync=1").unwrap();
+///
+/// assert!(matches!(url.stream_ref, StreamRef::CommitId(_)));
+/// assert_eq!(url.path, "/content");
+/// assert_eq!(url.param("sync"), Some("1"));
+/// assert_eq!(
+///     url.to_string(),
+///     "ceramic://k1dpgaqe3i64kjqcp801r3sn7ysi5i0k7nxvs7j351s7kewfzr3l7mdxnj7szwo4kr9mn2qki5nnj0cv836ythy1t1gya9s25cn1nexst3jxi5o3h6qprfyju/content?sync=1"
+/// );
+/// assert_eq!(CeramicUrl::from_str(&url.to_string()).unwrap(), url);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CeramicUrl {
+    /// The stream or commit.
+    pub stream_ref: StreamRef,
+
+    /// Path following the ID, empty or starting with `/`.
+    pub path: String,
+
+    /// Query parameters other than `commit` and `version`.
+    pub query: Vec<(String, String)>,
+}
+
+impl CeramicUrl {
+    /// Get the first value of a query parameter.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Parse with the given [`ParseOptions`] for the ID.
+    pub fn from_str_with(s: &str, options: &ParseOptions) -> Result<Self> {
+        let err = |stage, offset, expected: &str, actual: &str| -> Error {
+            ParseError::new(stage, offset, expected, actual)
+                .with_input(s)
+                .into()
+        };
+
+        let rest = s
+            .strip_prefix(SCHEME)
+            .ok_or_else(|| err(ParseStage::Url, 0, SCHEME, "other scheme"))?;
+        if let Some(offset) = s.find(|c| !is_url_char(c)) {
+            let c = s[offset..].chars().next().unwrap_or_default();
+            return Err(err(
+                ParseStage::Url,
+                offset,
+                "URL character",
+                &format!("{c:?}"),
+            ));
+        }
+
+        let (rest, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let (id, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+        let mut offset = SCHEME.len() + id.len();
+        for segment in path.split('/') {
+            // `url::Url` would resolve dot segments, percent-encoded or not, changing the path
+            if util::is_dot_segment(segment) {
+                return Err(err(ParseStage::Url, offset, "path segment", segment));
+            }
+            offset += segment.len() + 1;
+        }
+
+        let mut stream_ref = StreamRef::from_str_with(id, options).map_err(|e| match e {
+            Error::Parse(mut e) => {
+                if matches!(e.stage, ParseStage::Url | ParseStage::Multibase) {
+                    e.offset += SCHEME.len();
+                }
+                e.with_input(s).into()
+            }
+            e => e,
+        })?;
+
+        let mut commit: Option<Option<Cid>> = None;
+        let mut params = Vec::new();
+        let mut offset = s.len() - query.len();
+        for param in query.split('&') {
+            let (key, value) = param.split_once('=').unwrap_or((param, ""));
+            match key {
+                "commit" | "version" => {
+                    let offset = offset + key.len() + 1;
+                    let cid = match value {
+                        // Zero commit
+                        "0" => None,
+                        cid => Some(Cid::from_str(cid).map_err(|e| {
+                            err(ParseStage::CommitCid, offset, "CID or 0", &e.to_string())
+                        })?),
+                    };
+                    match commit {
+                        Some(previous) if previous != cid => {
+                            return Err(err(
+                                ParseStage::CommitCid,
+                                offset,
+                                "the same commit as `commit` and `version`",
+                                value,
+                            ))
+                        }
+                        _ => commit = Some(cid),
+                    }
+                }
+                "" if value.is_empty() => {}
+                _ => params.push((key.to_string(), value.to_string())),
+            }
+            offset += param.len() + 1;
+        }
+
+        if let Some(commit) = commit {
+            let base = stream_ref.to_base_id();
+            stream_ref = StreamRef::CommitId(CommitId {
+                stream_type: base.stream_type,
+                cid: base.cid,
+                commit,
+            });
+        }
+
+        Ok(Self {
+            stream_ref,
+            path: path.to_string(),
+            query: params,
+        })
+    }
+}
+
+impl From<StreamRef> for CeramicUrl {
+    fn from(stream_ref: StreamRef) -> Self {
+        Self {
+            stream_ref,
+            path: String::new(),
+            query: Vec::new(),
+        }
+    }
+}
+
+impl From<StreamId> for CeramicUrl {
+    fn from(stream_id: StreamId) -> Self {
+        StreamRef::StreamId(stream_id).into()
+    }
+}
+
+impl From<CommitId> for CeramicUrl {
+    fn from(commit_id: CommitId) -> Self {
+        StreamRef::CommitId(commit_id).into()
+    }
+}
+
+impl fmt::Display for CeramicUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.stream_ref.to_url(), self.path)?;
+        for (i, (key, value)) in self.query.iter().enumerate() {
+            let separator = if i == 0 { '?' } else { '&' };
+            write!(f, "{separator}{key}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for CeramicUrl {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::from_str_with(s, &ParseOptions::default())
+    }
+}
+
+#[cfg(feature = "url")]
+impl TryFrom<&CeramicUrl> for url::Url {
+    type Error = url::ParseError;
+
+    fn try_from(url: &CeramicUrl) -> Result<Self, url::ParseError> {
+        url::Url::parse(&url.to_string())
+    }
+}
+
+#[cfg(feature = "url")]
+impl TryFrom<CeramicUrl> for url::Url {
+    type Error = url::ParseError;
+
+    fn try_from(url: CeramicUrl) -> Result<Self, url::ParseError> {
+        url::Url::try_from(&url)
+    }
+}
+
+#[cfg(feature = "url")]
+impl TryFrom<&url::Url> for CeramicUrl {
+    type Error = Error;
+
+    fn try_from(url: &url::Url) -> Result<Self> {
+        Self::from_str(url.as_str())
+    }
+}
+
+#[cfg(feature = "url")]
+impl TryFrom<url::Url> for CeramicUrl {
+    type Error = Error;
+
+    fn try_from(url: url::Url) -> Result<Self> {
+        Self::try_from(&url)
+    }
+}
+
+// RFC 3986 characters allowed in a path or query, which `url::Url` keeps as written.
+fn is_url_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "-._~!$&'()*+,;=:@/?%".contains(c)
+}
@@ -1,17 +1,28 @@
-use std::{fmt, str::FromStr};
+use alloc::{format, string::String, vec::Vec};
+use core::{cmp::Ordering, fmt, ops::Deref, str::FromStr};
 
 use cid::Cid;
-use unsigned_varint::encode as varint;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::{util, *};
+use crate::{
+    io::{self, Read},
+    util, *,
+};
 
 /// Commit identifier, includes type, genesis CID, commit CID.
 ///
 /// Encoded as `<multibase-prefix><multicodec-streamid><type><genesis-cid-bytes><commit-cid-bytes>`.
 ///
 /// String representation is base36-encoding of the bytes above.
-
+///
+/// Ordered like the encoded bytes, by [`StreamId`] and then commit, the zero commit first, see
+/// [`StreamRef`].
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Text, sql_type = diesel::sql_types::Binary)
+)]
 pub struct CommitId {
     pub stream_type: StreamType,
     pub cid: Cid,
@@ -24,9 +35,79 @@ impl CommitId {
         self.commit.unwrap_or(self.cid)
     }
 
+    /// Whether the [`CommitId`] points at the genesis commit, explicitly or not.
+    pub fn is_genesis(&self) -> bool {
+        self.commit() == self.cid
+    }
+
+    /// Represent the genesis commit as [`None`], the form encoded with a zero commit.
+    pub fn normalize(&mut self) {
+        if self.is_genesis() {
+            self.commit = None;
+        }
+    }
+
+    /// Get the normalized [`CommitId`], see [`CommitId::normalize`].
+    pub fn canonical(&self) -> Self {
+        let mut commit_id = self.clone();
+        commit_id.normalize();
+        commit_id
+    }
+
     /// Parse from slice of bytes.
     pub fn from_slice<I: AsRef<[u8]>>(value: I) -> Result<Self> {
-        util::try_from_slice::<true, false>(value.as_ref()).map(|stream_ref| {
+        Self::from_slice_with(value, &ParseOptions::default())
+    }
+
+    /// Parse from slice of bytes with the given [`ParseOptions`].
+    pub fn from_slice_with<I: AsRef<[u8]>>(value: I, options: &ParseOptions) -> Result<Self> {
+        util::try_from_slice::<true, false>(value.as_ref(), options).map(|stream_ref| {
+            if let StreamRef::CommitId(commit_id) = stream_ref {
+                commit_id
+            } else {
+                unreachable!()
+            }
+        })
+    }
+
+    /// Read a [`CommitId`] from a byte stream, bytes following the commit CID are left unread.
+    pub fn read_bytes<R: io::Read>(r: R) -> Result<Self> {
+        Self::read_bytes_with(r, &ParseOptions::default())
+    }
+
+    /// Read a [`CommitId`] from a byte stream with the given [`ParseOptions`].
+    ///
+    /// [`ParseOptions::strict`] rejects a commit CID equal to the genesis CID.
+    pub fn read_bytes_with<R: io::Read>(mut r: R, options: &ParseOptions) -> Result<Self> {
+        let base = StreamId::read_bytes_with(&mut r, options)?;
+
+        let mut first = [0];
+        r.read_exact(&mut first)?;
+        let commit = match first {
+            // Zero commit
+            [0] => None,
+            first => Some(Cid::read_bytes(first.as_slice().chain(r))?),
+        };
+        if options.strict && commit == Some(base.cid) {
+            return Err(ParseError::new(
+                ParseStage::CommitCid,
+                base.encoded_len(),
+                "zero commit for genesis",
+                "genesis CID",
+            )
+            .into());
+        }
+
+        Ok(CommitId {
+            stream_type: base.stream_type,
+            cid: base.cid,
+            commit,
+        })
+    }
+
+    /// Parse from a string or URL with the given [`ParseOptions`].
+    pub fn from_str_with(s: &str, options: &ParseOptions) -> Result<Self> {
+        util::from_str::<true, false>(s, options).map(|stream_ref| {
             if let StreamRef::CommitId(commit_id) = stream_ref {
                 commit_id
             } else {
@@ -36,6 +117,57 @@ impl CommitId {
     }
 }
 
+/// A normalized [`CommitId`], comparing and hashing by resolved commit.
+///
+/// Unlike [`CommitId`], a commit of [`None`] and an explicit genesis commit are equal, so the
+/// same point in history is a single key in a map.
+///
+/// ```rust
+/// # use streamid::*;
+/// # let cid = cid::Cid::default();
+/// let zero = CommitId { stream_type: StreamType::Tile, cid, commit: None };
+/// let explicit = CommitId { stream_type: StreamType::Tile, cid, commit: Some(cid) };
+///
+/// assert_ne!(zero, explicit);
+/// assert_eq!(CanonicalCommitId::from(zero), CanonicalCommitId::from(explicit));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CanonicalCommitId(CommitId);
+
+impl CanonicalCommitId {
+    /// Get the normalized [`CommitId`].
+    pub fn into_inner(self) -> CommitId {
+        self.0
+    }
+}
+
+impl From<CommitId> for CanonicalCommitId {
+    fn from(mut commit_id: CommitId) -> Self {
+        commit_id.normalize();
+        Self(commit_id)
+    }
+}
+
+impl From<CanonicalCommitId> for CommitId {
+    fn from(commit_id: CanonicalCommitId) -> Self {
+        commit_id.0
+    }
+}
+
+impl Deref for CanonicalCommitId {
+    type Target = CommitId;
+
+    fn deref(&self) -> &CommitId {
+        &self.0
+    }
+}
+
+impl fmt::Display for CanonicalCommitId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
 impl StreamRefExt for CommitId {
     fn stream_type(&self) -> StreamType {
         self.stream_type
@@ -61,21 +193,59 @@ impl StreamRefExt for CommitId {
     }
 
     fn to_bytes(&self) -> Vec<u8> {
-        let mut codec_buf = varint::u8_buffer();
-        let codec = varint::u8(STREAMID_CODEC, &mut codec_buf);
+        let mut bytes = Vec::with_capacity(self.encoded_len());
+        self.write_bytes(&mut bytes)
+            .expect("writing to a Vec never fails");
+        bytes
+    }
 
-        let mut stream_type_buf = varint::u8_buffer();
-        let stream_type = varint::u8(self.stream_type.into(), &mut stream_type_buf);
+    fn encoded_len(&self) -> usize {
+        let commit_len = self.commit.as_ref().map(Cid::encoded_len).unwrap_or(1);
 
-        let cid_bytes = self.cid.to_bytes();
+        self.to_base_id().encoded_len() + commit_len
+    }
 
-        let commit_bytes = self
-            .commit
-            .as_ref()
-            .map(Cid::to_bytes)
-            .unwrap_or_else(|| vec![0]);
+    fn write_bytes<W: io::Write>(&self, mut w: W) -> Result<usize> {
+        let base_len = self.to_base_id().write_bytes(&mut w)?;
 
-        [codec, stream_type, &cid_bytes, &commit_bytes].concat()
+        let commit_len = match &self.commit {
+            Some(commit) => commit.write_bytes(&mut w)?,
+            None => {
+                // Zero commit
+                w.write_all(&[0])?;
+                1
+            }
+        };
+
+        Ok(base_len + commit_len)
+    }
+
+    fn to_legacy_url(&self) -> String {
+        match &self.commit {
+            Some(commit) => format!("/ceramic/{}?commit={commit}", self.to_base_id()),
+            // Zero commit
+            None => format!("/ceramic/{}?commit=0", self.to_base_id()),
+        }
+    }
+}
+
+impl PartialOrd for CommitId {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CommitId {
+    fn cmp(&self, other: &Self) -> Ordering {
+        util::cmp_varint(self.stream_type.code(), other.stream_type.code())
+            .then_with(|| util::cmp_cid(&self.cid, &other.cid))
+            .then_with(|| match (&self.commit, &other.commit) {
+                (None, None) => Ordering::Equal,
+                // The zero commit byte sorts before the first byte of any CID
+                (None, Some(_)) => Ordering::Less,
+                (Some(_), None) => Ordering::Greater,
+                (Some(commit), Some(other)) => util::cmp_cid(commit, other),
+            })
     }
 }
 
@@ -89,7 +259,19 @@ impl FromStr for CommitId {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        util::from_str::<true, false>(s).map(|stream_ref| {
+        Self::from_str_with(s, &ParseOptions::default())
+    }
+}
+
+impl Serialize for CommitId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        util::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CommitId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        util::deserialize::<true, false, _>(deserializer).map(|stream_ref| {
             if let StreamRef::CommitId(commit_id) = stream_ref {
                 commit_id
             } else {
@@ -0,0 +1,159 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use core::{fmt, str::FromStr};
+
+use cid::{
+    multibase::{decode, Base},
+    Cid,
+};
+
+use crate::{util, *};
+
+/// What an input string is, as explained by [`diagnose`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputKind {
+    /// A [`StreamId`], possibly of an unknown [`StreamType`].
+    StreamId(StreamId),
+
+    /// A [`CommitId`], possibly of an unknown [`StreamType`].
+    CommitId(CommitId),
+
+    /// A bare [`Cid`], missing the streamid codec and stream type.
+    Cid(Cid),
+
+    /// A `did:key` DID.
+    DidKey,
+
+    /// Nothing recognized.
+    Invalid,
+}
+
+/// Explanation of an input string, see [`diagnose`].
+///
+/// Displays as a human-readable sentence.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnosis {
+    /// What the input is.
+    pub kind: InputKind,
+
+    /// Multibase encoding of the input, if it decodes as multibase.
+    pub base: Option<Base>,
+
+    /// Corrected form of the input, where one exists.
+    pub suggestion: Option<String>,
+
+    /// Error parsing the input as a [`StreamRef`] with the default [`ParseOptions`].
+    pub error: Option<ParseError>,
+}
+
+/// Explain what an input string is, and how to correct it where possible.
+///
+/// ```rust
+/// # use streamid::*;
+/// let diagnosis = diagnose("bagcqcerakszw2vsovxznyp5gfnpdj4cqm2xiv76yd24wkjewhhykovorwo6a");
+///
+/// assert!(matches!(diagnosis.kind, InputKind::Cid(_)));
+/// assert_eq!(
+///     diagnosis.to_string(),
+///     "bare CID, not a StreamID: missing the streamid codec and stream type",
+/// );
+/// ```
+pub fn diagnose(s: &str) -> Diagnosis {
+    let s = s.trim();
+    let base = util::encoded(s).and_then(|encoded| decode(encoded).ok().map(|(base, _)| base));
+
+    let error = match util::from_str::<true, true>(s, &ParseOptions::default()) {
+        Err(Error::Parse(err)) => Some(err),
+        _ => None,
+    };
+
+    let options = ParseOptions {
+        allow_unknown_stream_types: true,
+        ..Default::default()
+    };
+    let (kind, suggestion) = match util::from_str::<true, true>(s, &options) {
+        Ok(StreamRef::StreamId(stream_id)) => {
+            let canonical = stream_id.to_string();
+            let suggestion = Some(canonical).filter(|canonical| canonical != s);
+            (InputKind::StreamId(stream_id), suggestion)
+        }
+        Ok(StreamRef::CommitId(commit_id)) => {
+            let suggestion = Some(commit_id.to_base_id().to_string());
+            (InputKind::CommitId(commit_id), suggestion)
+        }
+        Err(_) if s.starts_with("did:key:") => (InputKind::DidKey, None),
+        Err(_) => match util::encoded(s).map(Cid::from_str) {
+            Some(Ok(cid)) => (InputKind::Cid(cid), None),
+            _ => (InputKind::Invalid, None),
+        },
+    };
+
+    Diagnosis {
+        kind,
+        base,
+        suggestion,
+        error,
+    }
+}
+
+impl fmt::Display for Diagnosis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            InputKind::StreamId(stream_id) => {
+                write!(
+                    f,
+                    "StreamID of {} stream",
+                    stream_type(stream_id.stream_type)
+                )?;
+            }
+            InputKind::CommitId(commit_id) => write!(
+                f,
+                "CommitID of {} stream at commit {}, not a StreamID",
+                stream_type(commit_id.stream_type),
+                commit_id.commit()
+            )?,
+            InputKind::Cid(_) => {
+                f.write_str("bare CID, not a StreamID: missing the streamid codec and stream type")?
+            }
+            InputKind::DidKey => f.write_str("did:key DID, not a StreamID")?,
+            InputKind::Invalid => match &self.error {
+                Some(err) => write!(f, "not a StreamID: {err}")?,
+                None => f.write_str("not a StreamID")?,
+            },
+        }
+
+        match (&self.kind, self.base) {
+            (InputKind::Cid(_), _) | (_, Some(Base::Base36Lower) | None) => {}
+            (InputKind::Invalid, Some(base)) => write!(f, ", {} multibase", base_name(base))?,
+            (_, Some(base)) => write!(f, ", {} multibase rather than base36", base_name(base))?,
+        }
+
+        match (&self.kind, &self.suggestion) {
+            (InputKind::CommitId(_), Some(suggestion)) => {
+                write!(f, "; its StreamID is {suggestion}")
+            }
+            (_, Some(suggestion)) => write!(f, "; canonical form is {suggestion}"),
+            (_, None) => Ok(()),
+        }
+    }
+}
+
+fn stream_type(stream_type: StreamType) -> String {
+    match stream_type {
+        StreamType::Unknown(unknown) => format!("unknown stream type {unknown}"),
+        stream_type => format!("{stream_type}"),
+    }
+}
+
+fn base_name(base: Base) -> String {
+    match base {
+        Base::Base36Lower => "base36".into(),
+        Base::Base32Lower => "base32".into(),
+        Base::Base58Btc => "base58btc".into(),
+        Base::Base64Url => "base64url".into(),
+        Base::Base16Lower => "base16".into(),
+        base => format!("unsupported {base:?}"),
+    }
+}
@@ -0,0 +1,320 @@
+//! C-compatible bindings, see `include/streamid.h`.
+//!
+//! Parsed IDs are opaque [`StreamRef`] handles owned by the caller and released with
+//! [`streamid_free`]. Functions returning a [`StreamIdError`] write their result to an out
+//! pointer, strings they return are released with [`streamid_string_free`].
+
+use std::{
+    ffi::{c_char, CStr, CString},
+    ptr, slice,
+    str::FromStr,
+};
+
+use cid::Cid;
+
+use crate::*;
+
+/// Error codes returned by the FFI functions, derived from `Error`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StreamIdError {
+    Ok = 0,
+    NullPointer = 1,
+    InvalidUtf8 = 2,
+    BufferTooSmall = 3,
+
+    ParseUrl = 10,
+    ParseMultibase = 11,
+    ParseCodec = 12,
+    ParseStreamType = 13,
+    ParseGenesisCid = 14,
+    ParseCommitCid = 15,
+    ParseTrailingData = 16,
+    ParseKind = 17,
+
+    InvalidStreamRefIpld = 20,
+    InvalidStreamTypeIndex = 21,
+    InvalidStreamTypeName = 22,
+    UnknownCidVersion = 23,
+    CborEncoding = 24,
+    Cid = 25,
+    Io = 26,
+    Multihash = 27,
+    VarintDecode = 28,
+}
+
+impl From<&Error> for StreamIdError {
+    fn from(err: &Error) -> Self {
+        match err {
+            Error::Parse(err) => match err.stage {
+                ParseStage::Url => StreamIdError::ParseUrl,
+                ParseStage::Multibase => StreamIdError::ParseMultibase,
+                ParseStage::Codec => StreamIdError::ParseCodec,
+                ParseStage::StreamType => StreamIdError::ParseStreamType,
+                ParseStage::GenesisCid => StreamIdError::ParseGenesisCid,
+                ParseStage::CommitCid => StreamIdError::ParseCommitCid,
+                ParseStage::TrailingData => StreamIdError::ParseTrailingData,
+                ParseStage::Kind => StreamIdError::ParseKind,
+            },
+            Error::InvalidStreamRefIpld(_) => StreamIdError::InvalidStreamRefIpld,
+            Error::InvalidStreamTypeIndex(_) => StreamIdError::InvalidStreamTypeIndex,
+            Error::InvalidStreamTypeName(_) => StreamIdError::InvalidStreamTypeName,
+            Error::UnknownCidVersion(_) => StreamIdError::UnknownCidVersion,
+            Error::CborEncoding(_) => StreamIdError::CborEncoding,
+            Error::Cid(_) => StreamIdError::Cid,
+            Error::Io(_) => StreamIdError::Io,
+            Error::Multihash(_) => StreamIdError::Multihash,
+            Error::VarintDecode(_) => StreamIdError::VarintDecode,
+        }
+    }
+}
+
+/// Parse a `StreamRef` from a NUL-terminated string or URL into `*out`.
+///
+/// # Safety
+///
+/// `s` must be a valid NUL-terminated string and `out` valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn streamid_from_str(
+    s: *const c_char,
+    out: *mut *mut StreamRef,
+) -> StreamIdError {
+    if s.is_null() || out.is_null() {
+        return StreamIdError::NullPointer;
+    }
+    let s = match CStr::from_ptr(s).to_str() {
+        Ok(s) => s,
+        Err(_) => return StreamIdError::InvalidUtf8,
+    };
+
+    write_handle(StreamRef::from_str(s), out)
+}
+
+/// Parse a `StreamRef` from `len` bytes into `*out`.
+///
+/// # Safety
+///
+/// `bytes` must be valid for reads of `len` bytes and `out` valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn streamid_from_bytes(
+    bytes: *const u8,
+    len: usize,
+    out: *mut *mut StreamRef,
+) -> StreamIdError {
+    if bytes.is_null() || out.is_null() {
+        return StreamIdError::NullPointer;
+    }
+
+    write_handle(
+        StreamRef::from_slice(slice::from_raw_parts(bytes, len)),
+        out,
+    )
+}
+
+/// Create a `CommitId` of the same stream at the commit CID string into `*out`, `"0"` for the
+/// genesis commit as in legacy URLs.
+///
+/// # Safety
+///
+/// `stream_ref` must be a live handle, `commit` a valid NUL-terminated string and `out` valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn streamid_at_commit(
+    stream_ref: *const StreamRef,
+    commit: *const c_char,
+    out: *mut *mut StreamRef,
+) -> StreamIdError {
+    if stream_ref.is_null() || commit.is_null() || out.is_null() {
+        return StreamIdError::NullPointer;
+    }
+    let commit = match CStr::from_ptr(commit).to_str() {
+        Ok(commit) => commit,
+        Err(_) => return StreamIdError::InvalidUtf8,
+    };
+
+    let commit_id = match commit {
+        // Zero commit
+        "0" => Ok((*stream_ref).at_commit(*(*stream_ref).cid()).canonical()),
+        commit => Cid::from_str(commit)
+            .map(|commit| (*stream_ref).at_commit(commit))
+            .map_err(Error::from),
+    };
+    write_handle(commit_id.map(StreamRef::CommitId), out)
+}
+
+/// Release a handle, null is ignored.
+///
+/// # Safety
+///
+/// `stream_ref` must be null or a handle not yet released.
+#[no_mangle]
+pub unsafe extern "C" fn streamid_free(stream_ref: *mut StreamRef) {
+    if !stream_ref.is_null() {
+        drop(Box::from_raw(stream_ref));
+    }
+}
+
+/// Get the stream type code.
+///
+/// Returns `UINT64_MAX` when `stream_ref` is null.
+///
+/// # Safety
+///
+/// `stream_ref` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn streamid_stream_type(stream_ref: *const StreamRef) -> u64 {
+    if stream_ref.is_null() {
+        return u64::MAX;
+    }
+    (*stream_ref).stream_type().code()
+}
+
+/// Whether the handle is a `CommitId` rather than a `StreamId`.
+///
+/// Returns false when `stream_ref` is null.
+///
+/// # Safety
+///
+/// `stream_ref` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn streamid_is_commit_id(stream_ref: *const StreamRef) -> bool {
+    if stream_ref.is_null() {
+        return false;
+    }
+    matches!(*stream_ref, StreamRef::CommitId(_))
+}
+
+/// Get the genesis CID string.
+///
+/// Returns null when `stream_ref` is null.
+///
+/// # Safety
+///
+/// `stream_ref` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn streamid_cid(stream_ref: *const StreamRef) -> *mut c_char {
+    if stream_ref.is_null() {
+        return ptr::null_mut();
+    }
+    into_c_string((*stream_ref).cid().to_string())
+}
+
+/// Get the commit CID string, the genesis CID for the genesis commit, null for a `StreamId`.
+///
+/// Returns null when `stream_ref` is null.
+///
+/// # Safety
+///
+/// `stream_ref` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn streamid_commit(stream_ref: *const StreamRef) -> *mut c_char {
+    match stream_ref.as_ref() {
+        Some(StreamRef::CommitId(commit_id)) => into_c_string(commit_id.commit().to_string()),
+        Some(StreamRef::StreamId(_)) | None => ptr::null_mut(),
+    }
+}
+
+/// Encode into a base36 string.
+///
+/// Returns null when `stream_ref` is null.
+///
+/// # Safety
+///
+/// `stream_ref` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn streamid_to_base36(stream_ref: *const StreamRef) -> *mut c_char {
+    if stream_ref.is_null() {
+        return ptr::null_mut();
+    }
+    into_c_string((*stream_ref).to_base36_string())
+}
+
+/// Encode into a base36 URL.
+///
+/// Returns null when `stream_ref` is null.
+///
+/// # Safety
+///
+/// `stream_ref` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn streamid_to_url(stream_ref: *const StreamRef) -> *mut c_char {
+    if stream_ref.is_null() {
+        return ptr::null_mut();
+    }
+    into_c_string((*stream_ref).to_url())
+}
+
+/// Get the length in bytes of the encoded handle.
+///
+/// Returns 0 when `stream_ref` is null.
+///
+/// # Safety
+///
+/// `stream_ref` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn streamid_encoded_len(stream_ref: *const StreamRef) -> usize {
+    if stream_ref.is_null() {
+        return 0;
+    }
+    (*stream_ref).encoded_len()
+}
+
+/// Encode into `buf` of `buf_len` bytes, writing the encoded length to `*out_len`.
+///
+/// Fails with `StreamIdError::BufferTooSmall` when `buf_len` is less than
+/// `streamid_encoded_len`, `*out_len` is still written.
+///
+/// # Safety
+///
+/// `stream_ref` must be a live handle, `buf` valid for writes of `buf_len` bytes and `out_len`
+/// valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn streamid_to_bytes(
+    stream_ref: *const StreamRef,
+    buf: *mut u8,
+    buf_len: usize,
+    out_len: *mut usize,
+) -> StreamIdError {
+    if stream_ref.is_null() || buf.is_null() || out_len.is_null() {
+        return StreamIdError::NullPointer;
+    }
+
+    let len = (*stream_ref).encoded_len();
+    *out_len = len;
+    if buf_len < len {
+        return StreamIdError::BufferTooSmall;
+    }
+
+    match (*stream_ref).write_bytes(slice::from_raw_parts_mut(buf, len)) {
+        Ok(_) => StreamIdError::Ok,
+        Err(err) => StreamIdError::from(&err),
+    }
+}
+
+/// Release a string returned by this library, null is ignored.
+///
+/// # Safety
+///
+/// `s` must be null or a string returned by this library not yet released.
+#[no_mangle]
+pub unsafe extern "C" fn streamid_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+unsafe fn write_handle(stream_ref: Result<StreamRef>, out: *mut *mut StreamRef) -> StreamIdError {
+    match stream_ref {
+        Ok(stream_ref) => {
+            *out = Box::into_raw(Box::new(stream_ref));
+            StreamIdError::Ok
+        }
+        Err(err) => StreamIdError::from(&err),
+    }
+}
+
+fn into_c_string(s: String) -> *mut c_char {
+    CString::new(s)
+        .expect("encoded IDs and CIDs contain no NUL bytes")
+        .into_raw()
+}
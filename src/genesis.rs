@@ -0,0 +1,303 @@
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use libipld::{cbor::DagCborCodec, prelude::*, Ipld};
+
+use crate::*;
+
+/// Header of a genesis commit, with the fields and encodings of the JS implementation.
+///
+/// Unset fields are left out of the commit. `schema` is written as a CommitID string, `model`
+/// and `context` as StreamID bytes.
+///
+/// ```rust
+/// # use streamid::*;
+/// let header = GenesisHeader::new(["did:key:z6MkgSV3tAuw7gUWqKCUY7ae6uWNxqYgdwPhUJbJhF9EFXm9"])
+///     .family("IDX")
+///     .tags(["profile"]);
+///
+/// let _stream_id = GenesisCommit::new(header).stream_id(StreamType::Tile).unwrap();
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GenesisHeader {
+    /// DIDs controlling the stream.
+    pub controllers: Vec<String>,
+
+    /// Family of the stream.
+    pub family: Option<String>,
+
+    /// Tags of the stream.
+    pub tags: Option<Vec<String>>,
+
+    /// Commit of the schema the content follows.
+    pub schema: Option<CommitId>,
+
+    /// Model of a model instance document.
+    pub model: Option<StreamId>,
+
+    /// Value making the genesis commit unique, left out for deterministic streams.
+    pub unique: Option<GenesisUnique>,
+
+    /// Name of the separator field, `model` for model instance documents.
+    pub sep: Option<String>,
+
+    /// Whether the stream is indexed, `shouldIndex` in the commit.
+    pub should_index: Option<bool>,
+
+    /// Context the stream was created in.
+    pub context: Option<StreamId>,
+}
+
+impl GenesisHeader {
+    /// Create a header with the given controllers.
+    pub fn new<I: IntoIterator<Item = S>, S: Into<String>>(controllers: I) -> Self {
+        Self {
+            controllers: controllers.into_iter().map(Into::into).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the family.
+    pub fn family(self, family: impl Into<String>) -> Self {
+        Self {
+            family: Some(family.into()),
+            ..self
+        }
+    }
+
+    /// Set the tags.
+    pub fn tags<I: IntoIterator<Item = S>, S: Into<String>>(self, tags: I) -> Self {
+        Self {
+            tags: Some(tags.into_iter().map(Into::into).collect()),
+            ..self
+        }
+    }
+
+    /// Set the schema commit.
+    pub fn schema(self, schema: CommitId) -> Self {
+        Self {
+            schema: Some(schema),
+            ..self
+        }
+    }
+
+    /// Set the model.
+    pub fn model(self, model: StreamId) -> Self {
+        Self {
+            model: Some(model),
+            ..self
+        }
+    }
+
+    /// Set the unique value.
+    pub fn unique(self, unique: impl Into<GenesisUnique>) -> Self {
+        Self {
+            unique: Some(unique.into()),
+            ..self
+        }
+    }
+
+    /// Set the separator field name.
+    pub fn sep(self, sep: impl Into<String>) -> Self {
+        Self {
+            sep: Some(sep.into()),
+            ..self
+        }
+    }
+
+    /// Set whether the stream is indexed.
+    pub fn should_index(self, should_index: bool) -> Self {
+        Self {
+            should_index: Some(should_index),
+            ..self
+        }
+    }
+
+    /// Set the context.
+    pub fn context(self, context: StreamId) -> Self {
+        Self {
+            context: Some(context),
+            ..self
+        }
+    }
+}
+
+impl From<&GenesisHeader> for Ipld {
+    fn from(header: &GenesisHeader) -> Self {
+        let mut map = BTreeMap::new();
+        map.insert(
+            "controllers".to_string(),
+            Ipld::List(
+                header
+                    .controllers
+                    .iter()
+                    .cloned()
+                    .map(Ipld::String)
+                    .collect(),
+            ),
+        );
+        if let Some(family) = &header.family {
+            map.insert("family".to_string(), Ipld::String(family.clone()));
+        }
+        if let Some(tags) = &header.tags {
+            map.insert(
+                "tags".to_string(),
+                Ipld::List(tags.iter().cloned().map(Ipld::String).collect()),
+            );
+        }
+        if let Some(schema) = &header.schema {
+            map.insert("schema".to_string(), Ipld::String(schema.to_string()));
+        }
+        if let Some(model) = &header.model {
+            map.insert("model".to_string(), Ipld::Bytes(model.to_bytes()));
+        }
+        if let Some(unique) = &header.unique {
+            map.insert("unique".to_string(), Ipld::from(unique));
+        }
+        if let Some(sep) = &header.sep {
+            map.insert("sep".to_string(), Ipld::String(sep.clone()));
+        }
+        if let Some(should_index) = header.should_index {
+            map.insert("shouldIndex".to_string(), Ipld::Bool(should_index));
+        }
+        if let Some(context) = &header.context {
+            map.insert("context".to_string(), Ipld::Bytes(context.to_bytes()));
+        }
+        Ipld::Map(map)
+    }
+}
+
+impl From<GenesisHeader> for Ipld {
+    fn from(header: GenesisHeader) -> Self {
+        Ipld::from(&header)
+    }
+}
+
+/// The `unique` field of a [`GenesisHeader`].
+///
+/// The JS `TileDocument` writes a base64 string of random bytes, model instance documents write
+/// bytes.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum GenesisUnique {
+    String(String),
+    Bytes(Vec<u8>),
+}
+
+impl From<String> for GenesisUnique {
+    fn from(unique: String) -> Self {
+        GenesisUnique::String(unique)
+    }
+}
+
+impl From<&str> for GenesisUnique {
+    fn from(unique: &str) -> Self {
+        GenesisUnique::String(unique.to_string())
+    }
+}
+
+impl From<Vec<u8>> for GenesisUnique {
+    fn from(unique: Vec<u8>) -> Self {
+        GenesisUnique::Bytes(unique)
+    }
+}
+
+impl From<&[u8]> for GenesisUnique {
+    fn from(unique: &[u8]) -> Self {
+        GenesisUnique::Bytes(unique.to_vec())
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for GenesisUnique {
+    fn from(unique: [u8; N]) -> Self {
+        GenesisUnique::Bytes(unique.to_vec())
+    }
+}
+
+impl From<&GenesisUnique> for Ipld {
+    fn from(unique: &GenesisUnique) -> Self {
+        match unique {
+            GenesisUnique::String(unique) => Ipld::String(unique.clone()),
+            GenesisUnique::Bytes(unique) => Ipld::Bytes(unique.clone()),
+        }
+    }
+}
+
+/// An unsigned genesis commit, `{ header, data }`.
+///
+/// `data` is left out of the commit when [`None`], set it to [`Ipld::Null`] for an explicit
+/// `data: null`.
+///
+/// ```rust
+/// # use std::str::FromStr;
+/// #
+/// # use streamid::*;
+/// let model = StreamId::from_str("kjzl6hvfrbw6c5ajfmes842lu09vjxu5956e3xq0xk12gp2jcf9s90cagt2god9").unwrap();
+/// let header = GenesisHeader::new(["did:key:z6MkgSV3tAuw7gUWqKCUY7ae6uWNxqYgdwPhUJbJhF9EFXm9"])
+///     .model(model)
+///     .sep("model")
+///     .unique([0x01, 0x02, 0x03]);
+/// let genesis = GenesisCommit::new(header).data(Ipld::Map(
+///     [("name".to_string(), Ipld::from("Alice"))].into_iter().collect(),
+/// ));
+///
+/// let stream_id = genesis.stream_id(StreamType::Mid).unwrap();
+/// assert_eq!(
+///     stream_id,
+///     StreamId::from_genesis(StreamType::Mid, &Ipld::from(&genesis)).unwrap()
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct GenesisCommit {
+    /// The header.
+    pub header: GenesisHeader,
+
+    /// The initial content.
+    pub data: Option<Ipld>,
+}
+
+impl GenesisCommit {
+    /// Create a genesis commit without data.
+    pub fn new(header: GenesisHeader) -> Self {
+        Self { header, data: None }
+    }
+
+    /// Set the initial content.
+    pub fn data(self, data: impl Into<Ipld>) -> Self {
+        Self {
+            data: Some(data.into()),
+            ..self
+        }
+    }
+
+    /// Encode as DAG-CBOR, the bytes hashed into the genesis CID.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        DagCborCodec
+            .encode(&Ipld::from(self))
+            .map_err(|err| Error::CborEncoding(err.to_string()))
+    }
+
+    /// Get the [`StreamId`] of the stream created by this commit.
+    pub fn stream_id(&self, stream_type: StreamType) -> Result<StreamId> {
+        StreamId::from_genesis(stream_type, &Ipld::from(self))
+    }
+}
+
+impl From<&GenesisCommit> for Ipld {
+    fn from(genesis: &GenesisCommit) -> Self {
+        let mut map = BTreeMap::new();
+        map.insert("header".to_string(), Ipld::from(&genesis.header));
+        if let Some(data) = &genesis.data {
+            map.insert("data".to_string(), data.clone());
+        }
+        Ipld::Map(map)
+    }
+}
+
+impl From<GenesisCommit> for Ipld {
+    fn from(genesis: GenesisCommit) -> Self {
+        Ipld::from(&genesis)
+    }
+}
@@ -0,0 +1,150 @@
+use std::{
+    io::{Read, Seek, Write},
+    str::FromStr,
+};
+
+use libipld::codec::{Codec, Decode, Encode};
+
+use crate::*;
+
+// IDs are represented as `Ipld::Bytes` of their binary form, the string form is accepted when
+// converting back.
+impl From<&StreamId> for Ipld {
+    fn from(stream_id: &StreamId) -> Self {
+        Ipld::Bytes(stream_id.to_bytes())
+    }
+}
+
+impl From<StreamId> for Ipld {
+    fn from(stream_id: StreamId) -> Self {
+        Ipld::from(&stream_id)
+    }
+}
+
+impl From<&CommitId> for Ipld {
+    fn from(commit_id: &CommitId) -> Self {
+        Ipld::Bytes(commit_id.to_bytes())
+    }
+}
+
+impl From<CommitId> for Ipld {
+    fn from(commit_id: CommitId) -> Self {
+        Ipld::from(&commit_id)
+    }
+}
+
+impl From<&StreamRef> for Ipld {
+    fn from(stream_ref: &StreamRef) -> Self {
+        Ipld::Bytes(stream_ref.to_bytes())
+    }
+}
+
+impl From<StreamRef> for Ipld {
+    fn from(stream_ref: StreamRef) -> Self {
+        Ipld::from(&stream_ref)
+    }
+}
+
+impl TryFrom<Ipld> for StreamId {
+    type Error = Error;
+
+    fn try_from(ipld: Ipld) -> Result<Self> {
+        match ipld {
+            Ipld::Bytes(bytes) => Self::from_slice(bytes),
+            Ipld::String(s) => Self::from_str(&s),
+            ipld => Err(Error::InvalidStreamRefIpld(ipld_kind(&ipld))),
+        }
+    }
+}
+
+impl TryFrom<Ipld> for CommitId {
+    type Error = Error;
+
+    fn try_from(ipld: Ipld) -> Result<Self> {
+        match ipld {
+            Ipld::Bytes(bytes) => Self::from_slice(bytes),
+            Ipld::String(s) => Self::from_str(&s),
+            ipld => Err(Error::InvalidStreamRefIpld(ipld_kind(&ipld))),
+        }
+    }
+}
+
+impl TryFrom<Ipld> for StreamRef {
+    type Error = Error;
+
+    fn try_from(ipld: Ipld) -> Result<Self> {
+        match ipld {
+            Ipld::Bytes(bytes) => Self::from_slice(bytes),
+            Ipld::String(s) => Self::from_str(&s),
+            ipld => Err(Error::InvalidStreamRefIpld(ipld_kind(&ipld))),
+        }
+    }
+}
+
+impl<C: Codec> Encode<C> for StreamId
+where
+    Ipld: Encode<C>,
+{
+    fn encode<W: Write>(&self, c: C, w: &mut W) -> libipld::error::Result<()> {
+        Ipld::from(self).encode(c, w)
+    }
+}
+
+impl<C: Codec> Decode<C> for StreamId
+where
+    Ipld: Decode<C>,
+{
+    fn decode<R: Read + Seek>(c: C, r: &mut R) -> libipld::error::Result<Self> {
+        Ok(Self::try_from(Ipld::decode(c, r)?)?)
+    }
+}
+
+impl<C: Codec> Encode<C> for CommitId
+where
+    Ipld: Encode<C>,
+{
+    fn encode<W: Write>(&self, c: C, w: &mut W) -> libipld::error::Result<()> {
+        Ipld::from(self).encode(c, w)
+    }
+}
+
+impl<C: Codec> Decode<C> for CommitId
+where
+    Ipld: Decode<C>,
+{
+    fn decode<R: Read + Seek>(c: C, r: &mut R) -> libipld::error::Result<Self> {
+        Ok(Self::try_from(Ipld::decode(c, r)?)?)
+    }
+}
+
+impl<C: Codec> Encode<C> for StreamRef
+where
+    Ipld: Encode<C>,
+{
+    fn encode<W: Write>(&self, c: C, w: &mut W) -> libipld::error::Result<()> {
+        Ipld::from(self).encode(c, w)
+    }
+}
+
+impl<C: Codec> Decode<C> for StreamRef
+where
+    Ipld: Decode<C>,
+{
+    fn decode<R: Read + Seek>(c: C, r: &mut R) -> libipld::error::Result<Self> {
+        Ok(Self::try_from(Ipld::decode(c, r)?)?)
+    }
+}
+
+pub(crate) fn ipld_kind(ipld: &Ipld) -> &'static str {
+    match ipld {
+        Ipld::Null => "null",
+        Ipld::Bool(_) => "bool",
+        Ipld::Integer(_) => "integer",
+        Ipld::Float(_) => "float",
+        Ipld::String(_) => "string",
+        Ipld::Bytes(_) => "bytes",
+        Ipld::List(_) => "list",
+        Ipld::Map(_) => "map",
+        Ipld::Link(_) => "link",
+    }
+}
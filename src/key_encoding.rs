@@ -0,0 +1,170 @@
+use alloc::vec::Vec;
+
+use cid::Cid;
+
+use crate::{util, *};
+
+const GENESIS: u8 = 0x00;
+const COMMIT: u8 = 0x01;
+const UPPER_BOUND: u8 = 0x02;
+
+/// Order-preserving keys for key-value stores, grouping a stream's [`CommitId`]s after its
+/// [`StreamId`].
+///
+/// A key is the encoded [`StreamId`], followed for a [`CommitId`] by `0x00` for the genesis commit
+/// or `0x01` and the commit CID. Every key of a stream starts with
+/// [`stream_key_prefix`](KeyEncoding::stream_key_prefix) and sorts below
+/// [`stream_key_upper_bound`](KeyEncoding::stream_key_upper_bound), so a range scan between them
+/// returns the [`StreamId`], the genesis commit and then the other commits ordered by CID bytes.
+///
+/// Keys are canonical like [`CanonicalCommitId`]: a commit equal to the genesis CID is encoded as
+/// the genesis commit and decoded with a commit of [`None`], and decoding rejects `0x01` followed
+/// by the genesis CID so each commit has a single key. Unknown stream types are kept when
+/// decoding, so keys written by newer versions stay readable.
+///
+/// ```rust
+/// # use std::str::FromStr;
+/// #
+/// # use streamid::*;
+/// let commit_id = CommitId::from_str("k1dpgaqe3i64kjqcp801r3sn7ysi5i0k7nxvs7j351s7kewfzr3l7mdxnj7szwo4kr9mn2qki5nnj0cv836ythy1t1gya9s25cn1nexst3jxi5o3h6qprfyju").unwrap();
+/// let stream_id = commit_id.to_base_id();
+///
+/// let key = commit_id.to_key();
+/// assert!(key.starts_with(&stream_id.stream_key_prefix()));
+/// assert!(stream_id.to_key() < key && key < stream_id.stream_key_upper_bound());
+/// assert_eq!(CommitId::from_key(&key).unwrap(), commit_id);
+/// ```
+pub trait KeyEncoding: StreamRefExt + Sized {
+    /// Encode as a key.
+    fn to_key(&self) -> Vec<u8>;
+
+    /// Decode a key written by [`to_key`](KeyEncoding::to_key).
+    fn from_key(key: &[u8]) -> Result<Self>;
+
+    /// Get the prefix shared by the keys of the stream, the key of its [`StreamId`].
+    fn stream_key_prefix(&self) -> Vec<u8> {
+        self.to_base_id().to_bytes()
+    }
+
+    /// Get the exclusive upper bound of the keys of the stream.
+    fn stream_key_upper_bound(&self) -> Vec<u8> {
+        let mut key = self.stream_key_prefix();
+        key.push(UPPER_BOUND);
+        key
+    }
+}
+
+impl KeyEncoding for StreamId {
+    fn to_key(&self) -> Vec<u8> {
+        self.to_bytes()
+    }
+
+    fn from_key(key: &[u8]) -> Result<Self> {
+        match decode(key)? {
+            StreamRef::StreamId(stream_id) => Ok(stream_id),
+            StreamRef::CommitId(commit_id) => Err(kind_error(
+                key,
+                commit_id.to_base_id().encoded_len(),
+                "StreamID",
+                "CommitID",
+            )),
+        }
+    }
+}
+
+impl KeyEncoding for CommitId {
+    fn to_key(&self) -> Vec<u8> {
+        let mut key = self.stream_key_prefix();
+        match self.commit {
+            Some(commit) if commit != self.cid => {
+                key.push(COMMIT);
+                key.extend(commit.to_bytes());
+            }
+            _ => key.push(GENESIS),
+        }
+        key
+    }
+
+    fn from_key(key: &[u8]) -> Result<Self> {
+        match decode(key)? {
+            StreamRef::CommitId(commit_id) => Ok(commit_id),
+            StreamRef::StreamId(_) => Err(kind_error(key, key.len(), "CommitID", "StreamID")),
+        }
+    }
+}
+
+impl KeyEncoding for StreamRef {
+    fn to_key(&self) -> Vec<u8> {
+        match self {
+            StreamRef::StreamId(stream_id) => stream_id.to_key(),
+            StreamRef::CommitId(commit_id) => commit_id.to_key(),
+        }
+    }
+
+    fn from_key(key: &[u8]) -> Result<Self> {
+        decode(key)
+    }
+}
+
+fn decode(key: &[u8]) -> Result<StreamRef> {
+    let options = ParseOptions {
+        allow_unknown_stream_types: true,
+        ..Default::default()
+    };
+
+    let mut r = key;
+    let stream_type = util::read_header(&mut r, &options)?;
+    let cid = Cid::read_bytes(&mut r)?;
+    let offset = key.len() - r.len();
+
+    let (commit, rest) = match r {
+        [] => return Ok(StreamRef::StreamId(StreamId { stream_type, cid })),
+        [GENESIS, rest @ ..] => (None, rest),
+        [COMMIT, commit @ ..] => {
+            let (commit, rest) = util::read_cid(commit)?;
+            if commit == cid {
+                return Err(ParseError::new(
+                    ParseStage::CommitCid,
+                    offset + 1,
+                    "commit other than genesis",
+                    "genesis CID",
+                )
+                .with_input(util::to_hex(key))
+                .into());
+            }
+            (Some(commit), rest)
+        }
+        [kind, ..] => {
+            return Err(ParseError::new(
+                ParseStage::Kind,
+                offset,
+                "end of input, genesis 0x00 or commit 0x01",
+                format_args!("{kind:#04x}"),
+            )
+            .with_input(util::to_hex(key))
+            .into())
+        }
+    };
+    if !rest.is_empty() {
+        return Err(ParseError::new(
+            ParseStage::TrailingData,
+            key.len() - rest.len(),
+            "end of input",
+            format_args!("{} bytes", rest.len()),
+        )
+        .with_input(util::to_hex(key))
+        .into());
+    }
+
+    Ok(StreamRef::CommitId(CommitId {
+        stream_type,
+        cid,
+        commit,
+    }))
+}
+
+fn kind_error(key: &[u8], offset: usize, expected: &str, actual: &str) -> Error {
+    ParseError::new(ParseStage::Kind, offset, expected, actual)
+        .with_input(util::to_hex(key))
+        .into()
+}
@@ -1,19 +1,59 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![doc = include_str!("../README.md")]
 
+extern crate alloc;
+
+mod ceramic_url;
 mod commit_id;
+mod diagnose;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "genesis")]
+mod genesis;
+#[cfg(feature = "ipld")]
+mod ipld;
+mod key_encoding;
+#[cfg(feature = "macros")]
+#[doc(hidden)]
+pub mod macros;
+mod parse_options;
+#[cfg(feature = "python")]
+pub mod python;
 mod result;
+#[cfg(feature = "sql")]
+pub mod sql;
 mod stream_id;
+mod stream_path;
 mod stream_ref;
+mod stream_ref_view;
 mod stream_type;
 mod util;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(not(feature = "std"))]
+use core2::io;
+#[cfg(feature = "std")]
+use std::io;
 
 pub use cid::Cid;
+#[cfg(feature = "ipld")]
 pub use libipld::Ipld;
+#[cfg(feature = "macros")]
+pub use streamid_macros::{commit_id, stream_id};
 
+pub use ceramic_url::*;
 pub use commit_id::*;
+pub use diagnose::*;
+#[cfg(feature = "genesis")]
+pub use genesis::*;
+pub use key_encoding::*;
+pub use parse_options::*;
 pub use result::*;
 pub use stream_id::*;
+pub use stream_path::*;
 pub use stream_ref::*;
+pub use stream_ref_view::*;
 pub use stream_type::*;
 
 pub const STREAMID_CODEC: u8 = 206;
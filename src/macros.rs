@@ -0,0 +1,37 @@
+//! Support for the `stream_id!` and `commit_id!` macros, not public API.
+
+use core::mem::ManuallyDrop;
+
+use cid::{
+    multihash::{self, MultihashGeneric},
+    Cid, Version,
+};
+
+type Multihash = MultihashGeneric<64>;
+type MultihashResult = Result<Multihash, multihash::Error>;
+type CidResult = Result<Cid, cid::Error>;
+
+/// Build a [`Cid`] from parts validated by the macros, in constant contexts.
+///
+/// The errors have destructors that can not run in constant contexts, so the results are never
+/// dropped and the `Copy` values are read through a reference instead.
+pub const fn cid(version: u64, codec: u64, code: u64, digest: &[u8]) -> Cid {
+    let hash = ManuallyDrop::new(Multihash::wrap(code, digest));
+    // SAFETY: `ManuallyDrop<T>` has the same layout as `T`.
+    let hash = unsafe { &*(&hash as *const ManuallyDrop<_> as *const MultihashResult) };
+    let hash = match hash {
+        Ok(hash) => *hash,
+        Err(_) => panic!("invalid multihash"),
+    };
+
+    let version = match version {
+        0 => Version::V0,
+        _ => Version::V1,
+    };
+    let cid = ManuallyDrop::new(Cid::new(version, codec, hash));
+    // SAFETY: `ManuallyDrop<T>` has the same layout as `T`.
+    match unsafe { &*(&cid as *const ManuallyDrop<_> as *const CidResult) } {
+        Ok(cid) => *cid,
+        Err(_) => panic!("invalid CID"),
+    }
+}
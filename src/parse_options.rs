@@ -0,0 +1,37 @@
+/// Options controlling how a [`StreamRef`](crate::StreamRef),
+/// [`StreamId`](crate::StreamId) or [`CommitId`](crate::CommitId) is parsed.
+///
+/// The default options match [`FromStr`](core::str::FromStr) and `from_slice`.
+///
+/// ```rust
+/// # use streamid::*;
+/// let options = ParseOptions {
+///     allow_unknown_stream_types: true,
+///     ..Default::default()
+/// };
+///
+/// let stream_id = StreamId {
+///     stream_type: StreamType::from_code(300),
+///     cid: cid::Cid::default(),
+/// };
+/// let bytes = stream_id.to_bytes();
+///
+/// assert!(StreamId::from_slice(&bytes).is_err());
+/// assert_eq!(StreamId::from_slice_with(&bytes, &options).unwrap(), stream_id);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Keep stream type codes unknown to this crate as [`StreamType::Unknown`](crate::StreamType::Unknown)
+    /// instead of failing.
+    pub allow_unknown_stream_types: bool,
+
+    /// Only accept base36 strings, rather than any of base36, base32, base58btc, base64url and
+    /// hex.
+    pub base36_only: bool,
+
+    /// Reject every non-canonical encoding so that a [`StreamRef`](crate::StreamRef) has
+    /// exactly one valid spelling: strings must be bare base36 without URL prefix or `?commit=`
+    /// query, and bytes must not contain a commit CID equal to the genesis CID or data after the
+    /// commit. Non-minimal varints are rejected in every mode.
+    pub strict: bool,
+}
@@ -0,0 +1,381 @@
+//! Python bindings, built as the `streamid` extension module.
+//!
+//! Classes compare like their Rust types and hash and pickle as their encoded bytes.
+
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap},
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+
+use cid::Cid;
+use pyo3::{
+    basic::CompareOp,
+    exceptions::{PyTypeError, PyValueError},
+    prelude::*,
+    types::{PyBool, PyBytes, PyDict, PyFloat, PyList, PyLong, PyString, PyTuple},
+    PyTypeInfo,
+};
+
+use crate::*;
+
+/// Python `StreamType`, see [`StreamType`].
+#[pyclass(name = "StreamType", module = "streamid")]
+#[derive(Clone, Copy)]
+pub struct PyStreamType(StreamType);
+
+#[pymethods]
+impl PyStreamType {
+    #[classattr]
+    const TILE: Self = Self(StreamType::Tile);
+    #[classattr]
+    const CAIP10_LINK: Self = Self(StreamType::Caip10Link);
+    #[classattr]
+    const MODEL: Self = Self(StreamType::Model);
+    #[classattr]
+    const MID: Self = Self(StreamType::Mid);
+    #[classattr]
+    const UNLOADABLE: Self = Self(StreamType::Unloadable);
+
+    #[staticmethod]
+    fn from_code(code: u64) -> Self {
+        Self(StreamType::from_code(code))
+    }
+
+    #[staticmethod]
+    fn from_name(name: &str) -> PyResult<Self> {
+        StreamType::from_str(name).map(Self).map_err(py_error)
+    }
+
+    #[getter]
+    fn code(&self) -> u64 {
+        self.0.code()
+    }
+
+    #[getter]
+    fn name(&self) -> Option<&'static str> {
+        self.0.name()
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("StreamType({})", self.0.code())
+    }
+
+    fn __hash__(&self) -> u64 {
+        self.0.code()
+    }
+
+    fn __richcmp__(&self, other: Self, op: CompareOp) -> bool {
+        op.matches(self.0.code().cmp(&other.0.code()))
+    }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<(PyObject, (u64,))> {
+        let from_code = py.get_type::<Self>().getattr("from_code")?;
+        Ok((from_code.into(), (self.0.code(),)))
+    }
+}
+
+/// Python `StreamId`, see [`StreamId`].
+#[pyclass(name = "StreamId", module = "streamid")]
+#[derive(Clone)]
+pub struct PyStreamId(StreamId);
+
+#[pymethods]
+impl PyStreamId {
+    #[new]
+    fn new(stream_type: PyStreamType, cid: &str) -> PyResult<Self> {
+        let cid = Cid::from_str(cid).map_err(|err| py_error(err.into()))?;
+        Ok(Self(StreamId {
+            stream_type: stream_type.0,
+            cid,
+        }))
+    }
+
+    #[staticmethod]
+    fn from_str(s: &str) -> PyResult<Self> {
+        StreamId::from_str(s).map(Self).map_err(py_error)
+    }
+
+    /// Parse from bytes, see [`ParseOptions::allow_unknown_stream_types`].
+    #[staticmethod]
+    #[pyo3(signature = (bytes, allow_unknown_stream_types = false))]
+    fn from_bytes(bytes: &[u8], allow_unknown_stream_types: bool) -> PyResult<Self> {
+        let options = ParseOptions {
+            allow_unknown_stream_types,
+            ..Default::default()
+        };
+        StreamId::from_slice_with(bytes, &options)
+            .map(Self)
+            .map_err(py_error)
+    }
+
+    /// Create from a genesis commit given as a `dict`, see [`StreamId::from_genesis`].
+    #[staticmethod]
+    fn from_genesis(stream_type: PyStreamType, genesis: &PyDict) -> PyResult<Self> {
+        let genesis = to_ipld(genesis)?;
+        StreamId::from_genesis(stream_type.0, &genesis)
+            .map(Self)
+            .map_err(py_error)
+    }
+
+    #[getter]
+    fn stream_type(&self) -> PyStreamType {
+        PyStreamType(self.0.stream_type)
+    }
+
+    #[getter]
+    fn cid(&self) -> String {
+        self.0.cid.to_string()
+    }
+
+    /// Create a `CommitId` at the given commit CID, or `"0"` or `0` for the genesis commit.
+    fn at_commit(&self, commit: &PyAny) -> PyResult<PyCommitId> {
+        at_commit(&self.0, commit).map(PyCommitId)
+    }
+
+    fn to_base_id(&self) -> Self {
+        self.clone()
+    }
+
+    fn to_bytes<'py>(&self, py: Python<'py>) -> &'py PyBytes {
+        PyBytes::new(py, &self.0.to_bytes())
+    }
+
+    fn to_url(&self) -> String {
+        self.0.to_url()
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("StreamId('{}')", self.0)
+    }
+
+    fn __hash__(&self) -> u64 {
+        hash_bytes(&self.0.to_bytes())
+    }
+
+    fn __richcmp__(&self, other: Self, op: CompareOp) -> bool {
+        op.matches(self.0.cmp(&other.0))
+    }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<(PyObject, (PyObject, bool))> {
+        reduce::<Self>(py, &self.0.to_bytes())
+    }
+}
+
+/// Python `CommitId`, see [`CommitId`].
+#[pyclass(name = "CommitId", module = "streamid")]
+#[derive(Clone)]
+pub struct PyCommitId(CommitId);
+
+#[pymethods]
+impl PyCommitId {
+    #[new]
+    fn new(stream_type: PyStreamType, cid: &str, commit: Option<&str>) -> PyResult<Self> {
+        let cid = Cid::from_str(cid).map_err(|err| py_error(err.into()))?;
+        let commit = commit
+            .map(Cid::from_str)
+            .transpose()
+            .map_err(|err| py_error(err.into()))?;
+        Ok(Self(CommitId {
+            stream_type: stream_type.0,
+            cid,
+            commit,
+        }))
+    }
+
+    #[staticmethod]
+    fn from_str(s: &str) -> PyResult<Self> {
+        CommitId::from_str(s).map(Self).map_err(py_error)
+    }
+
+    /// Parse from bytes, see [`ParseOptions::allow_unknown_stream_types`].
+    #[staticmethod]
+    #[pyo3(signature = (bytes, allow_unknown_stream_types = false))]
+    fn from_bytes(bytes: &[u8], allow_unknown_stream_types: bool) -> PyResult<Self> {
+        let options = ParseOptions {
+            allow_unknown_stream_types,
+            ..Default::default()
+        };
+        CommitId::from_slice_with(bytes, &options)
+            .map(Self)
+            .map_err(py_error)
+    }
+
+    #[getter]
+    fn stream_type(&self) -> PyStreamType {
+        PyStreamType(self.0.stream_type)
+    }
+
+    #[getter]
+    fn cid(&self) -> String {
+        self.0.cid.to_string()
+    }
+
+    /// The commit CID, the genesis CID for the genesis commit.
+    #[getter]
+    fn commit(&self) -> String {
+        self.0.commit().to_string()
+    }
+
+    fn is_genesis(&self) -> bool {
+        self.0.is_genesis()
+    }
+
+    /// Create a `CommitId` at the given commit CID, or `"0"` or `0` for the genesis commit.
+    fn at_commit(&self, commit: &PyAny) -> PyResult<Self> {
+        at_commit(&self.0, commit).map(Self)
+    }
+
+    fn to_base_id(&self) -> PyStreamId {
+        PyStreamId(self.0.to_base_id())
+    }
+
+    fn to_bytes<'py>(&self, py: Python<'py>) -> &'py PyBytes {
+        PyBytes::new(py, &self.0.to_bytes())
+    }
+
+    fn to_url(&self) -> String {
+        self.0.to_url()
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("CommitId('{}')", self.0)
+    }
+
+    fn __hash__(&self) -> u64 {
+        hash_bytes(&self.0.to_bytes())
+    }
+
+    fn __richcmp__(&self, other: Self, op: CompareOp) -> bool {
+        op.matches(self.0.cmp(&other.0))
+    }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<(PyObject, (PyObject, bool))> {
+        reduce::<Self>(py, &self.0.to_bytes())
+    }
+}
+
+/// Python `StreamRef`, parsing either a `StreamId` or a `CommitId`, see [`StreamRef`].
+#[pyclass(name = "StreamRef", module = "streamid")]
+pub struct PyStreamRef;
+
+#[pymethods]
+impl PyStreamRef {
+    #[staticmethod]
+    fn from_str(py: Python<'_>, s: &str) -> PyResult<PyObject> {
+        StreamRef::from_str(s)
+            .map(|stream_ref| to_object(py, stream_ref))
+            .map_err(py_error)
+    }
+
+    #[staticmethod]
+    fn from_bytes(py: Python<'_>, bytes: &[u8]) -> PyResult<PyObject> {
+        StreamRef::from_slice(bytes)
+            .map(|stream_ref| to_object(py, stream_ref))
+            .map_err(py_error)
+    }
+}
+
+#[pymodule]
+fn streamid(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
+    m.add_class::<PyStreamType>()?;
+    m.add_class::<PyStreamId>()?;
+    m.add_class::<PyCommitId>()?;
+    m.add_class::<PyStreamRef>()?;
+    Ok(())
+}
+
+// Converts Python values to IPLD, dicts must have string keys.
+fn to_ipld(value: &PyAny) -> PyResult<Ipld> {
+    if value.is_none() {
+        Ok(Ipld::Null)
+    } else if let Ok(value) = value.downcast::<PyBool>() {
+        Ok(Ipld::Bool(value.is_true()))
+    } else if let Ok(value) = value.downcast::<PyLong>() {
+        Ok(Ipld::Integer(value.extract()?))
+    } else if let Ok(value) = value.downcast::<PyFloat>() {
+        Ok(Ipld::Float(value.value()))
+    } else if let Ok(value) = value.downcast::<PyString>() {
+        Ok(Ipld::String(value.to_str()?.into()))
+    } else if let Ok(value) = value.downcast::<PyBytes>() {
+        Ok(Ipld::Bytes(value.as_bytes().into()))
+    } else if let Ok(value) = value.downcast::<PyList>() {
+        value
+            .iter()
+            .map(to_ipld)
+            .collect::<PyResult<_>>()
+            .map(Ipld::List)
+    } else if let Ok(value) = value.downcast::<PyTuple>() {
+        value
+            .iter()
+            .map(to_ipld)
+            .collect::<PyResult<_>>()
+            .map(Ipld::List)
+    } else if let Ok(value) = value.downcast::<PyDict>() {
+        value
+            .iter()
+            .map(|(key, value)| Ok((key.extract::<String>()?, to_ipld(value)?)))
+            .collect::<PyResult<BTreeMap<_, _>>>()
+            .map(Ipld::Map)
+    } else {
+        Err(PyTypeError::new_err(format!(
+            "cannot convert {} to IPLD",
+            value.get_type().name()?
+        )))
+    }
+}
+
+fn to_object(py: Python<'_>, stream_ref: StreamRef) -> PyObject {
+    match stream_ref {
+        StreamRef::StreamId(stream_id) => PyStreamId(stream_id).into_py(py),
+        StreamRef::CommitId(commit_id) => PyCommitId(commit_id).into_py(py),
+    }
+}
+
+// A commit CID string, or `"0"` or `0` for the genesis commit as in legacy URLs.
+fn at_commit<T: StreamRefExt>(stream_ref: &T, commit: &PyAny) -> PyResult<CommitId> {
+    let genesis = || CommitId {
+        commit: None,
+        ..stream_ref.at_commit(*stream_ref.cid())
+    };
+    if let Ok(commit) = commit.downcast::<PyLong>() {
+        return match commit.extract::<u64>() {
+            Ok(0) => Ok(genesis()),
+            _ => Err(PyValueError::new_err("integer commit must be 0")),
+        };
+    }
+    match commit.extract::<&str>()? {
+        "0" => Ok(genesis()),
+        commit => Cid::from_str(commit)
+            .map(|commit| stream_ref.at_commit(commit))
+            .map_err(|err| py_error(err.into())),
+    }
+}
+
+// Pickles as a call to `from_bytes`, keeping unknown stream types.
+fn reduce<T: PyTypeInfo>(py: Python<'_>, bytes: &[u8]) -> PyResult<(PyObject, (PyObject, bool))> {
+    let from_bytes = py.get_type::<T>().getattr("from_bytes")?;
+    Ok((from_bytes.into(), (PyBytes::new(py, bytes).into(), true)))
+}
+
+fn hash_bytes(bytes: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn py_error(err: Error) -> PyErr {
+    PyValueError::new_err(err.to_string())
+}
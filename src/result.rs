@@ -1,50 +1,254 @@
-use thiserror::Error;
+use alloc::string::{String, ToString};
+use core::fmt;
 
-use crate::StreamType;
+use crate::io;
 
-pub type Result<T, E = Error> = std::result::Result<T, E>;
+pub type Result<T, E = Error> = core::result::Result<T, E>;
 
-#[derive(Error, Debug)]
+#[derive(Debug)]
 pub enum Error {
-    #[error("Error while parsing CommitID from bytes {0}: no commit information provided")]
-    InvalidCommitIdBytes(String),
+    Parse(ParseError),
+    InvalidStreamRefIpld(&'static str),
+    InvalidStreamTypeIndex(u64),
+    InvalidStreamTypeName(String),
+    UnknownCidVersion(u64),
+    CborEncoding(String),
+    Cid(cid::Error),
+    Io(io::Error),
+    Multihash(cid::multihash::Error),
+    VarintDecode(unsigned_varint::decode::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(err) => err.fmt(f),
+            Error::InvalidStreamRefIpld(kind) => {
+                write!(
+                    f,
+                    "Invalid StreamRef IPLD: expected bytes or string, got {kind}"
+                )
+            }
+            Error::InvalidStreamTypeIndex(code) => write!(f, "Invalid StreamType index: {code}"),
+            Error::InvalidStreamTypeName(name) => write!(f, "Invalid StreamType name: {name}"),
+            Error::UnknownCidVersion(version) => write!(f, "Unknown CID version {version}"),
+            Error::CborEncoding(err) => write!(f, "CBOR encoding error: {err}"),
+            Error::Cid(err) => err.fmt(f),
+            Error::Io(err) => err.fmt(f),
+            Error::Multihash(err) => err.fmt(f),
+            Error::VarintDecode(err) => err.fmt(f),
+        }
+    }
+}
 
-    #[error("Error while parsing CommitID from string {0}: no commit information provided")]
-    InvalidCommitIdString(String),
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        // Wrapped errors are transparent, they display as this error.
+        match self {
+            Error::Parse(err) => err.source(),
+            Error::Cid(err) => err.source(),
+            Error::Io(err) => err.source(),
+            Error::Multihash(err) => err.source(),
+            Error::VarintDecode(err) => err.source(),
+            _ => None,
+        }
+    }
+}
 
-    #[error("Invalid StreamID bytes {0}: contains commit")]
-    InvalidStreamIdBytes(String),
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
 
-    #[error("Invalid StreamID string {0}: contains commit")]
-    InvalidStreamIdString(String),
+impl From<cid::Error> for Error {
+    fn from(err: cid::Error) -> Self {
+        Error::Cid(err)
+    }
+}
 
-    #[error("Invalid StreamRef bytes: {0}")]
-    InvalidStreamRefBytes(String),
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
 
-    #[error("Invalid StreamRef, does not include streamid codec")]
-    InvalidStreamRefCodec,
+impl From<cid::multihash::Error> for Error {
+    fn from(err: cid::multihash::Error) -> Self {
+        Error::Multihash(err)
+    }
+}
 
-    #[error("Invalid StreamRef string: {0}")]
-    InvalidStreamRefString(String),
+impl From<unsigned_varint::decode::Error> for Error {
+    fn from(err: unsigned_varint::decode::Error) -> Self {
+        Error::VarintDecode(err)
+    }
+}
 
-    #[error("Invalid StreamType index: {0}")]
-    InvalidStreamTypeIndex(#[from] num_enum::TryFromPrimitiveError<StreamType>),
+/// Stage of parsing a [`StreamRef`](crate::StreamRef) at which a [`ParseError`] occurred.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ParseStage {
+    /// Matching the string against the accepted URL forms.
+    Url,
+    /// Decoding the multibase string.
+    Multibase,
+    /// Reading the streamid multicodec varint.
+    Codec,
+    /// Reading the stream type varint.
+    StreamType,
+    /// Reading the genesis CID.
+    GenesisCid,
+    /// Reading the commit CID, or the `commit` URL query.
+    CommitCid,
+    /// Checking for bytes following the [`StreamId`](crate::StreamId) or
+    /// [`CommitId`](crate::CommitId).
+    TrailingData,
+    /// Checking for a [`StreamId`](crate::StreamId) or [`CommitId`](crate::CommitId).
+    Kind,
+}
 
-    #[error("Invalid StreamType name: {0}")]
-    InvalidStreamTypeName(#[from] serde_plain::Error),
+impl fmt::Display for ParseStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ParseStage::Url => "URL",
+            ParseStage::Multibase => "multibase",
+            ParseStage::Codec => "codec varint",
+            ParseStage::StreamType => "type varint",
+            ParseStage::GenesisCid => "genesis CID",
+            ParseStage::CommitCid => "commit CID",
+            ParseStage::TrailingData => "trailing data",
+            ParseStage::Kind => "kind",
+        })
+    }
+}
 
-    #[error("Unknown CID version {0}")]
-    UnknownCidVersion(u64),
+/// Error parsing a [`StreamRef`](crate::StreamRef), [`StreamId`](crate::StreamId) or
+/// [`CommitId`](crate::CommitId) from a string or bytes.
+///
+/// ```rust
+/// # use streamid::*;
+/// let err = StreamId::from_slice([0xce, 0x01, 0x09]).unwrap_err();
+///
+/// let Error::Parse(err) = err else { panic!() };
+/// assert_eq!(err.stage, ParseStage::StreamType);
+/// assert_eq!(err.offset, 2);
+/// assert_eq!(err.actual, "9");
+/// assert_eq!(err.input, "ce0109");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// Stage at which parsing failed.
+    pub stage: ParseStage,
+
+    /// Offset of the failure in bytes, into the input of the stage that failed, for string and
+    /// byte input alike.
+    ///
+    /// [`ParseStage::Url`] and [`ParseStage::Multibase`] point into the input string, and so does
+    /// [`ParseStage::CommitCid`] for the `?commit=` query of a URL. The codec, stream type, CIDs
+    /// and trailing data point into the decoded bytes, also when parsing a string.
+    /// [`ParseStage::Kind`] points at the start of the multibase string when parsing a string and
+    /// at the end of the bytes when parsing bytes.
+    pub offset: usize,
+
+    /// Description of the expected value.
+    pub expected: String,
+
+    /// Description of the value found.
+    pub actual: String,
+
+    /// The input being parsed, hex-encoded when parsing bytes, empty when reading a stream.
+    pub input: String,
+}
 
-    #[error("CBOR encoding error: {0}")]
-    CborEncoding(String),
+impl ParseError {
+    pub(crate) fn new(
+        stage: ParseStage,
+        offset: usize,
+        expected: impl Into<String>,
+        actual: impl fmt::Display,
+    ) -> Self {
+        Self {
+            stage,
+            offset,
+            expected: expected.into(),
+            actual: actual.to_string(),
+            input: String::new(),
+        }
+    }
+
+    pub(crate) fn with_input(self, input: impl Into<String>) -> Self {
+        Self {
+            input: input.into(),
+            ..self
+        }
+    }
+}
 
-    #[error(transparent)]
-    Cid(#[from] cid::Error),
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Invalid StreamRef")?;
+        if !self.input.is_empty() {
+            write!(f, " {}", self.input)?;
+        }
+        write!(
+            f,
+            ": expected {} at {} offset {}, found {}",
+            self.expected, self.stage, self.offset, self.actual
+        )
+    }
+}
 
-    #[error(transparent)]
-    Multihash(#[from] cid::multihash::Error),
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// Error resolving a [`StreamPath`](crate::StreamPath) against stream content.
+///
+/// Each variant carries the pointer up to and including the token that failed.
+///
+/// ```rust
+/// # use std::str::FromStr;
+/// #
+/// # use streamid::*;
+/// let path = StreamPath::from_str("ceramic://kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s/name/first").unwrap();
+/// let err = path.resolve(&Ipld::Null).unwrap_err();
+///
+/// assert_eq!(
+///     err,
+///     ResolveError::NotAContainer {
+///         pointer: "/name".to_string(),
+///         kind: "null",
+///     }
+/// );
+/// ```
+#[cfg(feature = "ipld")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ResolveError {
+    /// A map has no entry for the token.
+    MissingKey { pointer: String },
+    /// A list index is past the end of the list, `-` included.
+    IndexOutOfBounds { pointer: String, len: usize },
+    /// A token indexing a list is not a decimal index without leading zeros.
+    InvalidIndex { pointer: String },
+    /// A token indexes a value that is not a map or list, with the kind of the value.
+    NotAContainer { pointer: String, kind: &'static str },
+}
 
-    #[error(transparent)]
-    VarintDecode(#[from] unsigned_varint::decode::Error),
+#[cfg(feature = "ipld")]
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResolveError::MissingKey { pointer } => write!(f, "No map entry at {pointer}"),
+            ResolveError::IndexOutOfBounds { pointer, len } => {
+                write!(f, "Index out of bounds at {pointer}: list length is {len}")
+            }
+            ResolveError::InvalidIndex { pointer } => write!(f, "Invalid list index at {pointer}"),
+            ResolveError::NotAContainer { pointer, kind } => {
+                write!(f, "Cannot index {kind} at {pointer}")
+            }
+        }
+    }
 }
+
+#[cfg(all(feature = "ipld", feature = "std"))]
+impl std::error::Error for ResolveError {}
@@ -0,0 +1,75 @@
+#[cfg(feature = "diesel-postgres")]
+use std::io::Write;
+use std::str::FromStr;
+
+#[cfg(feature = "diesel-postgres")]
+use diesel::pg::Pg;
+#[cfg(any(feature = "diesel-postgres", feature = "diesel-sqlite"))]
+use diesel::serialize::{self, Output, ToSql};
+#[cfg(feature = "diesel-sqlite")]
+use diesel::sqlite::Sqlite;
+use diesel::{
+    backend::Backend,
+    deserialize::{self, FromSql},
+    sql_types::{Binary, Text},
+};
+
+use crate::*;
+
+// `AsExpression` and `FromSqlRow` are derived on the types for both SQL types, reading is
+// generic over the backend, writing depends on how each backend collects bind values.
+macro_rules! impl_diesel {
+    ($($ty:ident),*) => {$(
+        impl<DB: Backend> FromSql<Text, DB> for $ty
+        where
+            String: FromSql<Text, DB>,
+        {
+            fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+                Ok($ty::from_str(&String::from_sql(bytes)?)?)
+            }
+        }
+
+        impl<DB: Backend> FromSql<Binary, DB> for $ty
+        where
+            Vec<u8>: FromSql<Binary, DB>,
+        {
+            fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+                Ok($ty::from_slice(Vec::<u8>::from_sql(bytes)?)?)
+            }
+        }
+
+        #[cfg(feature = "diesel-postgres")]
+        impl ToSql<Text, Pg> for $ty {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+                write!(out, "{self}")?;
+                Ok(serialize::IsNull::No)
+            }
+        }
+
+        #[cfg(feature = "diesel-postgres")]
+        impl ToSql<Binary, Pg> for $ty {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+                self.write_bytes(out)?;
+                Ok(serialize::IsNull::No)
+            }
+        }
+
+        #[cfg(feature = "diesel-sqlite")]
+        impl ToSql<Text, Sqlite> for $ty {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> serialize::Result {
+                out.set_value(self.to_string());
+                Ok(serialize::IsNull::No)
+            }
+        }
+
+        #[cfg(feature = "diesel-sqlite")]
+        impl ToSql<Binary, Sqlite> for $ty {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> serialize::Result {
+                out.set_value(self.to_bytes());
+                Ok(serialize::IsNull::No)
+            }
+        }
+    )*};
+}
+
+impl_diesel!(StreamId, CommitId, StreamRef);
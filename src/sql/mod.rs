@@ -0,0 +1,70 @@
+//! Database column types for sqlx, rusqlite and diesel.
+//!
+//! [`StreamId`](crate::StreamId), [`CommitId`](crate::CommitId) and
+//! [`StreamRef`](crate::StreamRef) are stored as their base36 string by default, and read back
+//! from any string accepted by `from_str`. Wrap them in [`Binary`] to store their encoded bytes in
+//! a `bytea` or `BLOB` column instead.
+//!
+//! diesel picks the storage from the SQL type of the column, so the IDs map to both `Text` and
+//! `Binary` columns without the wrapper.
+//!
+//! Values that fail to parse are reported as the [`Error`](crate::Error) of the parser, boxed in
+//! the error type of the database library.
+
+use core::{fmt, ops::Deref};
+
+#[cfg(feature = "diesel")]
+mod diesel;
+#[cfg(feature = "rusqlite")]
+mod rusqlite;
+#[cfg(feature = "sqlx")]
+mod sqlx;
+
+/// Store an ID as its encoded bytes instead of its base36 string.
+///
+/// ```rust
+/// # use std::str::FromStr;
+/// #
+/// # use streamid::{sql::Binary, *};
+/// // A row with a text `stream_id` column and a binary `commit_id` column.
+/// struct Event {
+///     stream_id: StreamId,
+///     commit_id: Binary<CommitId>,
+/// }
+///
+/// let commit_id = CommitId::from_str("k1dpgaqe3i64kjqcp801r3sn7ysi5i0k7nxvs7j351s7kewfzr3l7mdxnj7szwo4kr9mn2qki5nnj0cv836ythy1t1gya9s25cn1nexst3jxi5o3h6qprfyju").unwrap();
+/// let event = Event {
+///     stream_id: commit_id.to_base_id(),
+///     commit_id: Binary(commit_id),
+/// };
+/// assert_eq!(event.commit_id.to_base_id(), event.stream_id);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Binary<T>(pub T);
+
+impl<T> Binary<T> {
+    /// Get the wrapped ID.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Binary<T> {
+    fn from(id: T) -> Self {
+        Self(id)
+    }
+}
+
+impl<T> Deref for Binary<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Binary<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
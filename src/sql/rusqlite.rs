@@ -0,0 +1,38 @@
+use std::str::FromStr;
+
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+
+use super::Binary;
+use crate::*;
+
+macro_rules! impl_rusqlite {
+    ($($ty:ident),*) => {$(
+        impl ToSql for $ty {
+            fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+                Ok(self.to_string().into())
+            }
+        }
+
+        impl FromSql for $ty {
+            fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+                $ty::from_str(value.as_str()?).map_err(|err| FromSqlError::Other(err.into()))
+            }
+        }
+
+        impl FromSql for Binary<$ty> {
+            fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+                $ty::from_slice(value.as_blob()?)
+                    .map(Binary)
+                    .map_err(|err| FromSqlError::Other(err.into()))
+            }
+        }
+    )*};
+}
+
+impl_rusqlite!(StreamId, CommitId, StreamRef);
+
+impl<T: StreamRefExt> ToSql for Binary<T> {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(self.0.to_bytes().into())
+    }
+}
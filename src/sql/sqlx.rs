@@ -0,0 +1,81 @@
+use std::str::FromStr;
+
+use sqlx::{
+    database::{HasArguments, HasValueRef},
+    encode::IsNull,
+    error::BoxDynError,
+    Database, Decode, Encode, Type,
+};
+
+use super::Binary;
+use crate::*;
+
+// The impls are generic over the database, they are available for every database with string and
+// bytes columns, `TEXT` and `bytea` in Postgres, `TEXT` and `BLOB` in SQLite.
+macro_rules! impl_sqlx {
+    ($($ty:ident),*) => {$(
+        impl<DB: Database> Type<DB> for $ty
+        where
+            str: Type<DB>,
+        {
+            fn type_info() -> DB::TypeInfo {
+                <str as Type<DB>>::type_info()
+            }
+
+            fn compatible(ty: &DB::TypeInfo) -> bool {
+                <str as Type<DB>>::compatible(ty)
+            }
+        }
+
+        impl<'q, DB: Database> Encode<'q, DB> for $ty
+        where
+            String: Encode<'q, DB>,
+        {
+            fn encode_by_ref(&self, buf: &mut <DB as HasArguments<'q>>::ArgumentBuffer) -> IsNull {
+                self.to_string().encode(buf)
+            }
+        }
+
+        impl<'r, DB: Database> Decode<'r, DB> for $ty
+        where
+            &'r str: Decode<'r, DB>,
+        {
+            fn decode(value: <DB as HasValueRef<'r>>::ValueRef) -> Result<Self, BoxDynError> {
+                Ok($ty::from_str(<&str>::decode(value)?)?)
+            }
+        }
+
+        impl<'r, DB: Database> Decode<'r, DB> for Binary<$ty>
+        where
+            &'r [u8]: Decode<'r, DB>,
+        {
+            fn decode(value: <DB as HasValueRef<'r>>::ValueRef) -> Result<Self, BoxDynError> {
+                Ok(Binary($ty::from_slice(<&[u8]>::decode(value)?)?))
+            }
+        }
+    )*};
+}
+
+impl_sqlx!(StreamId, CommitId, StreamRef);
+
+impl<T, DB: Database> Type<DB> for Binary<T>
+where
+    [u8]: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <[u8] as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <[u8] as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, T: StreamRefExt, DB: Database> Encode<'q, DB> for Binary<T>
+where
+    Vec<u8>: Encode<'q, DB>,
+{
+    fn encode_by_ref(&self, buf: &mut <DB as HasArguments<'q>>::ArgumentBuffer) -> IsNull {
+        self.0.to_bytes().encode(buf)
+    }
+}
@@ -1,13 +1,17 @@
-use std::{fmt, str::FromStr};
+#[cfg(feature = "genesis")]
+use alloc::string::ToString;
+use alloc::{collections::BTreeMap, format, string::String, vec::Vec};
+use core::{cmp::Ordering, fmt, str::FromStr};
 
-use cid::{
-    multihash::{Code, MultihashDigest},
-    Cid,
-};
+use cid::Cid;
+#[cfg(feature = "genesis")]
 use libipld::{cbor::DagCborCodec, prelude::*};
+#[cfg(feature = "genesis")]
+use multihash::{Code, MultihashDigest};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use unsigned_varint::encode as varint;
 
-use crate::{util, *};
+use crate::{io, util, *};
 
 /// Stream identifier, no commit information included.
 ///
@@ -17,6 +21,8 @@ use crate::{util, *};
 ///
 /// String representation is base36-encoding of the bytes above.
 ///
+/// Ordered like the encoded bytes, by stream type code and then genesis CID, see [`StreamRef`].
+///
 /// ```rust
 /// # use std::str::FromStr;
 /// #
@@ -25,6 +31,11 @@ use crate::{util, *};
 /// let _stream_id = StreamId { stream_type: StreamType::Tile, cid };
 /// ```
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Text, sql_type = diesel::sql_types::Binary)
+)]
 pub struct StreamId {
     pub stream_type: StreamType,
     pub cid: Cid,
@@ -33,6 +44,8 @@ pub struct StreamId {
 impl StreamId {
     /// Create a [`StreamId`] from a genesis commit.
     ///
+    /// [`GenesisCommit`] builds unsigned genesis commits with the fields of the JS implementation.
+    ///
     /// ```rust
     /// # use streamid::*;
     /// let genesis = Ipld::Map(
@@ -54,8 +67,12 @@ impl StreamId {
     ///     .collect(),
     /// );
     ///
-    /// let _stream_id = StreamId::from_genesis(StreamType::Tile, &genesis);
+    /// let stream_id = StreamId::from_genesis(StreamType::Tile, &genesis).unwrap();
+    ///
+    /// let header = GenesisHeader::new(["did:3:kjz..."]).family("IDX");
+    /// assert_eq!(GenesisCommit::new(header).stream_id(StreamType::Tile).unwrap(), stream_id);
     /// ```
+    #[cfg(feature = "genesis")]
     pub fn from_genesis(stream_type: StreamType, genesis: &Ipld) -> Result<Self> {
         let bytes: Vec<u8> = DagCborCodec
             .encode(genesis)
@@ -65,8 +82,130 @@ impl StreamId {
         Ok(StreamId { stream_type, cid })
     }
 
+    /// Get the [`StreamId`] of the model instance document of a model with `accountRelation:
+    /// single` for a controller.
+    ///
+    /// The genesis commit is deterministic, `{ data: null, header: { controllers, model, sep } }`
+    /// with `sep` set to `model`.
+    ///
+    /// ```rust
+    /// # use std::str::FromStr;
+    /// #
+    /// # use streamid::*;
+    /// let model = StreamId::from_str("kjzl6hvfrbw6c5ajfmes842lu09vjxu5956e3xq0xk12gp2jcf9s90cagt2god9").unwrap();
+    /// let controller = "did:key:z6MkgSV3tAuw7gUWqKCUY7ae6uWNxqYgdwPhUJbJhF9EFXm9";
+    ///
+    /// let stream_id = StreamId::for_single_mid(&model, controller).unwrap();
+    /// assert_eq!(stream_id.stream_type, StreamType::Mid);
+    /// ```
+    #[cfg(feature = "genesis")]
+    pub fn for_single_mid(model: &StreamId, controller: &str) -> Result<Self> {
+        GenesisCommit::new(mid_header(model, controller))
+            .data(Ipld::Null)
+            .stream_id(StreamType::Mid)
+    }
+
+    /// Get the [`StreamId`] of the model instance document of a model with `accountRelation: set`
+    /// for a controller and the values of the model's unique fields.
+    ///
+    /// Like [`for_single_mid`](StreamId::for_single_mid), with the values joined by `|` as the
+    /// `unique` bytes of the header. The values are not escaped, so values containing `|` are
+    /// ambiguous: `["a|b"]` and `["a", "b"]` give the same [`StreamId`].
+    #[cfg(feature = "genesis")]
+    pub fn for_set_mid<I: IntoIterator<Item = S>, S: AsRef<str>>(
+        model: &StreamId,
+        controller: &str,
+        unique_values: I,
+    ) -> Result<Self> {
+        let unique = unique_values
+            .into_iter()
+            .map(|value| value.as_ref().to_string())
+            .collect::<Vec<_>>()
+            .join("|");
+
+        GenesisCommit::new(mid_header(model, controller).unique(unique.into_bytes()))
+            .data(Ipld::Null)
+            .stream_id(StreamType::Mid)
+    }
+
+    /// Get the entries for the commits of this stream from a map keyed by [`CommitId`], in
+    /// order, starting with the genesis commit.
+    ///
+    /// ```rust
+    /// # use std::{collections::BTreeMap, str::FromStr};
+    /// # use streamid::*;
+    /// let stream_id = StreamId::from_str("kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s").unwrap();
+    /// let commit = cid::Cid::from_str("bagjqcgzaday6dzalvmy5ady2m5a5legq5zrbsnlxfc2bfxej532ds7htpova").unwrap();
+    ///
+    /// let other = StreamId { stream_type: StreamType::Model, ..stream_id.clone() };
+    /// let genesis = CommitId { stream_type: stream_id.stream_type, cid: stream_id.cid, commit: None };
+    ///
+    /// let mut commits = BTreeMap::new();
+    /// commits.insert(other.at_commit(commit), "other");
+    /// commits.insert(stream_id.at_commit(commit), "update");
+    /// commits.insert(genesis, "genesis");
+    ///
+    /// let values: Vec<_> = stream_id.commits_in(&commits).map(|(_, value)| *value).collect();
+    /// assert_eq!(values, ["genesis", "update"]);
+    /// ```
+    pub fn commits_in<'a, V>(
+        &self,
+        map: &'a BTreeMap<CommitId, V>,
+    ) -> impl Iterator<Item = (&'a CommitId, &'a V)> + 'a {
+        let stream_id = self.clone();
+        let genesis = CommitId {
+            stream_type: self.stream_type,
+            cid: self.cid,
+            commit: None,
+        };
+
+        map.range(genesis..)
+            .take_while(move |(commit_id, _)| commit_id.to_base_id() == stream_id)
+    }
+
+    /// Get the entries for this stream and its commits from a map keyed by [`StreamRef`], in
+    /// order, starting with the [`StreamId`].
+    pub fn refs_in<'a, V>(
+        &self,
+        map: &'a BTreeMap<StreamRef, V>,
+    ) -> impl Iterator<Item = (&'a StreamRef, &'a V)> + 'a {
+        let stream_id = self.clone();
+
+        map.range(StreamRef::StreamId(self.clone())..)
+            .take_while(move |(stream_ref, _)| stream_ref.to_base_id() == stream_id)
+    }
+
+    /// Parse from slice of bytes.
     pub fn from_slice<I: AsRef<[u8]>>(value: I) -> Result<Self> {
-        util::try_from_slice::<false, true>(value.as_ref()).map(|stream_ref| {
+        Self::from_slice_with(value, &ParseOptions::default())
+    }
+
+    /// Parse from slice of bytes with the given [`ParseOptions`].
+    pub fn from_slice_with<I: AsRef<[u8]>>(value: I, options: &ParseOptions) -> Result<Self> {
+        util::try_from_slice::<false, true>(value.as_ref(), options).map(|stream_ref| {
+            if let StreamRef::StreamId(stream_id) = stream_ref {
+                stream_id
+            } else {
+                unreachable!()
+            }
+        })
+    }
+
+    /// Read a [`StreamId`] from a byte stream, bytes following the genesis CID are left unread.
+    pub fn read_bytes<R: io::Read>(r: R) -> Result<Self> {
+        Self::read_bytes_with(r, &ParseOptions::default())
+    }
+
+    /// Read a [`StreamId`] from a byte stream with the given [`ParseOptions`].
+    pub fn read_bytes_with<R: io::Read>(mut r: R, options: &ParseOptions) -> Result<Self> {
+        let stream_type = util::read_header(&mut r, options)?;
+        let cid = Cid::read_bytes(&mut r)?;
+        Ok(StreamId { stream_type, cid })
+    }
+
+    /// Parse from a string or URL with the given [`ParseOptions`].
+    pub fn from_str_with(s: &str, options: &ParseOptions) -> Result<Self> {
+        util::from_str::<false, true>(s, options).map(|stream_ref| {
             if let StreamRef::StreamId(stream_id) = stream_ref {
                 stream_id
             } else {
@@ -98,15 +237,51 @@ impl StreamRefExt for StreamId {
     }
 
     fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.encoded_len());
+        self.write_bytes(&mut bytes)
+            .expect("writing to a Vec never fails");
+        bytes
+    }
+
+    fn encoded_len(&self) -> usize {
         let mut codec_buf = varint::u8_buffer();
         let codec = varint::u8(STREAMID_CODEC, &mut codec_buf);
 
         let mut stream_type_buf = varint::u64_buffer();
-        let stream_type = varint::u64(self.stream_type as u64, &mut stream_type_buf);
+        let stream_type = varint::u64(self.stream_type.code(), &mut stream_type_buf);
+
+        codec.len() + stream_type.len() + self.cid.encoded_len()
+    }
 
-        let cid_bytes = self.cid.to_bytes();
+    fn write_bytes<W: io::Write>(&self, mut w: W) -> Result<usize> {
+        let mut codec_buf = varint::u8_buffer();
+        let codec = varint::u8(STREAMID_CODEC, &mut codec_buf);
+
+        let mut stream_type_buf = varint::u64_buffer();
+        let stream_type = varint::u64(self.stream_type.code(), &mut stream_type_buf);
 
-        [codec, stream_type, &cid_bytes].concat()
+        w.write_all(codec)?;
+        w.write_all(stream_type)?;
+        let cid_len = self.cid.write_bytes(&mut w)?;
+
+        Ok(codec.len() + stream_type.len() + cid_len)
+    }
+
+    fn to_legacy_url(&self) -> String {
+        format!("/ceramic/{self}")
+    }
+}
+
+impl PartialOrd for StreamId {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for StreamId {
+    fn cmp(&self, other: &Self) -> Ordering {
+        util::cmp_varint(self.stream_type.code(), other.stream_type.code())
+            .then_with(|| util::cmp_cid(&self.cid, &other.cid))
     }
 }
 
@@ -120,7 +295,19 @@ impl FromStr for StreamId {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        util::from_str::<false, true>(s).map(|stream_ref| {
+        Self::from_str_with(s, &ParseOptions::default())
+    }
+}
+
+impl Serialize for StreamId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        util::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for StreamId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        util::deserialize::<false, true, _>(deserializer).map(|stream_ref| {
             if let StreamRef::StreamId(stream_id) = stream_ref {
                 stream_id
             } else {
@@ -129,3 +316,10 @@ impl FromStr for StreamId {
         })
     }
 }
+
+#[cfg(feature = "genesis")]
+fn mid_header(model: &StreamId, controller: &str) -> GenesisHeader {
+    GenesisHeader::new([controller])
+        .model(model.clone())
+        .sep("model")
+}
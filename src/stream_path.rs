@@ -0,0 +1,271 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, str::FromStr};
+
+#[cfg(feature = "ipld")]
+use libipld::Ipld;
+
+use crate::*;
+
+/// A value inside the content of a stream, a [`StreamRef`] and an RFC 6901 JSON pointer.
+///
+/// Formats as a [`CeramicUrl`] whose path is the pointer, `ceramic://<id>/profile/name`. Tokens
+/// are escaped as in JSON pointers, `~0` for `~` and `~1` for `/`, then percent-encoded where
+/// they are not valid URL path segments. The empty pointer refers to the whole content.
+///
+/// The tokens `.` and `..` are rejected: URLs resolve them as dot segments, also when
+/// percent-encoded, so they have no URL form.
+///
+/// ```rust
+/// # use std::str::FromStr;
+/// #
+/// # use streamid::*;
+/// let path = StreamPath::from_str("ceramic://kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s/profile/a~1b%20c").unwrap();
+///
+/// assert_eq!(path.tokens, ["profile", "a/b c"]);
+/// assert_eq!(path.to_pointer(), "/profile/a~1b c");
+/// assert_eq!(StreamPath::from_str(&path.to_string()).unwrap(), path);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StreamPath {
+    /// The stream or commit.
+    pub stream_ref: StreamRef,
+
+    /// Unescaped reference tokens of the pointer, other than `.` and `..`.
+    pub tokens: Vec<String>,
+}
+
+impl StreamPath {
+    /// Create a [`StreamPath`] from a JSON pointer string, such as `/profile/name`.
+    pub fn new(stream_ref: StreamRef, pointer: &str) -> Result<Self> {
+        let err = |offset, expected: &str, actual: &str| -> Error {
+            ParseError::new(ParseStage::Url, offset, expected, actual)
+                .with_input(pointer)
+                .into()
+        };
+
+        let mut tokens = Vec::new();
+        if pointer.is_empty() {
+            return Ok(Self { stream_ref, tokens });
+        }
+        let Some(rest) = pointer.strip_prefix('/') else {
+            return Err(err(0, "'/'", &format!("{pointer:?}")));
+        };
+
+        let mut offset = 1;
+        for token in rest.split('/') {
+            if matches!(token, "." | "..") {
+                return Err(err(offset, "path segment", token));
+            }
+            tokens.push(unescape(token).map_err(|i| {
+                err(
+                    offset + i,
+                    "'~0' or '~1'",
+                    &token[i..].chars().take(2).collect::<String>(),
+                )
+            })?);
+            offset += token.len() + 1;
+        }
+
+        Ok(Self { stream_ref, tokens })
+    }
+
+    /// Format the tokens as a JSON pointer string.
+    pub fn to_pointer(&self) -> String {
+        self.tokens
+            .iter()
+            .map(|token| format!("/{}", escape(token)))
+            .collect()
+    }
+
+    /// Parse a `ceramic://` URL with the given [`ParseOptions`] for the ID.
+    ///
+    /// The URL may select a commit with the `commit` query parameter like a [`CeramicUrl`], other
+    /// parameters are rejected.
+    pub fn from_str_with(s: &str, options: &ParseOptions) -> Result<Self> {
+        let err = |offset, expected: &str, actual: &str| -> Error {
+            ParseError::new(ParseStage::Url, offset, expected, actual)
+                .with_input(s)
+                .into()
+        };
+
+        let url = CeramicUrl::from_str_with(s, options)?;
+        if let Some((key, _)) = url.query.first() {
+            let offset = s.find('?').unwrap_or_default();
+            return Err(err(offset, "end of input or commit", key));
+        }
+
+        let mut tokens = Vec::new();
+        let mut offset = s.find('?').unwrap_or(s.len()) - url.path.len();
+        for segment in url.path.split('/').skip(1) {
+            offset += 1;
+            let token = percent_decode(segment)
+                .ok_or_else(|| err(offset, "percent-encoded UTF-8", segment))?;
+            tokens.push(unescape(&token).map_err(|i| {
+                err(
+                    offset + encoded_offset(segment, i),
+                    "'~0' or '~1'",
+                    &token[i..].chars().take(2).collect::<String>(),
+                )
+            })?);
+            offset += segment.len();
+        }
+
+        Ok(Self {
+            stream_ref: url.stream_ref,
+            tokens,
+        })
+    }
+
+    /// Resolve the pointer against the content of the stream.
+    ///
+    /// Map keys are matched exactly, list indexes are decimal without leading zeros. Links are
+    /// not followed.
+    ///
+    /// ```rust
+    /// # use std::{collections::BTreeMap, str::FromStr};
+    /// #
+    /// # use streamid::*;
+    /// let content = Ipld::Map(BTreeMap::from([(
+    ///     "tags".to_string(),
+    ///     Ipld::List(vec![Ipld::String("ceramic".into())]),
+    /// )]));
+    /// let path = StreamPath::from_str("ceramic://kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s/tags/0").unwrap();
+    ///
+    /// assert_eq!(path.resolve(&content).unwrap(), &Ipld::String("ceramic".into()));
+    /// ```
+    #[cfg(feature = "ipld")]
+    pub fn resolve<'a>(&self, content: &'a Ipld) -> Result<&'a Ipld, ResolveError> {
+        let mut value = content;
+        for (i, token) in self.tokens.iter().enumerate() {
+            let pointer = || {
+                self.tokens[..=i]
+                    .iter()
+                    .map(|token| format!("/{}", escape(token)))
+                    .collect()
+            };
+
+            value = match value {
+                Ipld::Map(map) => map
+                    .get(token)
+                    .ok_or_else(|| ResolveError::MissingKey { pointer: pointer() })?,
+                Ipld::List(list) => {
+                    // `-` is the element after the last one.
+                    let index = match token.as_str() {
+                        "-" => list.len(),
+                        token => parse_index(token)
+                            .ok_or_else(|| ResolveError::InvalidIndex { pointer: pointer() })?,
+                    };
+                    list.get(index)
+                        .ok_or_else(|| ResolveError::IndexOutOfBounds {
+                            pointer: pointer(),
+                            len: list.len(),
+                        })?
+                }
+                value => {
+                    return Err(ResolveError::NotAContainer {
+                        pointer: pointer(),
+                        kind: crate::ipld::ipld_kind(value),
+                    })
+                }
+            };
+        }
+
+        Ok(value)
+    }
+}
+
+impl fmt::Display for StreamPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.stream_ref.to_url())?;
+        for token in &self.tokens {
+            write!(f, "/{}", percent_encode(&escape(token)))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for StreamPath {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::from_str_with(s, &ParseOptions::default())
+    }
+}
+
+fn escape(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+// Returns the offset of an invalid escape.
+fn unescape(token: &str) -> Result<String, usize> {
+    let mut unescaped = String::with_capacity(token.len());
+    let mut chars = token.char_indices();
+    while let Some((i, c)) = chars.next() {
+        unescaped.push(match c {
+            '~' => match chars.next() {
+                Some((_, '0')) => '~',
+                Some((_, '1')) => '/',
+                _ => return Err(i),
+            },
+            c => c,
+        });
+    }
+    Ok(unescaped)
+}
+
+// Percent-encodes an escaped token.
+fn percent_encode(token: &str) -> String {
+    token
+        .bytes()
+        .map(|byte| match byte {
+            byte if byte.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:@".contains(&byte) => {
+                char::from(byte).to_string()
+            }
+            byte => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+fn percent_decode(segment: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(segment.len());
+    let mut rest = segment.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = tail.get(..2)?;
+            if !hex.iter().all(u8::is_ascii_hexdigit) {
+                return None;
+            }
+            bytes.push(u8::from_str_radix(core::str::from_utf8(hex).ok()?, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+// Maps an offset in the percent-decoded segment to the offset in the segment.
+fn encoded_offset(segment: &str, decoded: usize) -> usize {
+    let bytes = segment.as_bytes();
+    let mut offset = 0;
+    for _ in 0..decoded {
+        offset += if bytes[offset] == b'%' { 3 } else { 1 };
+    }
+    offset
+}
+
+#[cfg(feature = "ipld")]
+fn parse_index(token: &str) -> Option<usize> {
+    let canonical = token == "0" || !token.starts_with('0');
+    if canonical && !token.is_empty() && token.bytes().all(|byte| byte.is_ascii_digit()) {
+        // Only overflow fails, an index past the end of any list.
+        Some(token.parse().unwrap_or(usize::MAX))
+    } else {
+        None
+    }
+}
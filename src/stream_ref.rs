@@ -1,22 +1,51 @@
-use std::{fmt, str::FromStr};
+use alloc::{format, string::String, vec::Vec};
+use core::{cmp::Ordering, fmt, str::FromStr};
 
 use cid::{
     multibase::{encode, Base},
     Cid,
 };
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::{util, *};
+use crate::{io, util, *};
 
 /// A [`CommitId`] or [`StreamId`].
+///
+/// Ordered like the encoded bytes, the same order as [`StreamRefView`]s, but compared field by
+/// field without allocating. A [`StreamId`] sorts directly before the [`CommitId`]s of its
+/// stream, and the genesis [`CommitId`] with a zero commit sorts first among those. See
+/// [`StreamId::refs_in`].
+///
+/// There is no `StreamRef::read_bytes`: the encoding does not mark whether a commit follows the
+/// genesis CID, so a [`StreamId`] followed by other bytes may also read as a [`CommitId`]. Byte
+/// streams holding either kind must record which one they hold, and read it back with
+/// [`StreamId::read_bytes`] or [`CommitId::read_bytes`]. Parse complete encodings with
+/// [`StreamRef::from_slice`].
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Text, sql_type = diesel::sql_types::Binary)
+)]
 pub enum StreamRef {
     CommitId(CommitId),
     StreamId(StreamId),
 }
 
 impl StreamRef {
+    /// Parse from slice of bytes.
     pub fn from_slice<I: AsRef<[u8]>>(value: I) -> Result<Self> {
-        util::try_from_slice::<true, true>(value.as_ref())
+        Self::from_slice_with(value, &ParseOptions::default())
+    }
+
+    /// Parse from slice of bytes with the given [`ParseOptions`].
+    pub fn from_slice_with<I: AsRef<[u8]>>(value: I, options: &ParseOptions) -> Result<Self> {
+        util::try_from_slice::<true, true>(value.as_ref(), options)
+    }
+
+    /// Parse from a string or URL with the given [`ParseOptions`].
+    pub fn from_str_with(s: &str, options: &ParseOptions) -> Result<Self> {
+        util::from_str::<true, true>(s, options)
     }
 }
 
@@ -36,15 +65,51 @@ pub trait StreamRefExt {
     /// Encode the [`StreamRef`] into bytes.
     fn to_bytes(&self) -> Vec<u8>;
 
+    /// Get the length in bytes of the encoded [`StreamRef`].
+    fn encoded_len(&self) -> usize {
+        self.to_bytes().len()
+    }
+
+    /// Write the encoded [`StreamRef`] to a byte stream, returns the number of bytes written.
+    ///
+    /// The encoding does not mark whether a commit follows the genesis CID, read it back with
+    /// [`StreamId::read_bytes`] or [`CommitId::read_bytes`].
+    fn write_bytes<W: io::Write>(&self, mut w: W) -> Result<usize> {
+        let bytes = self.to_bytes();
+        w.write_all(&bytes)?;
+        Ok(bytes.len())
+    }
+
+    /// Encode the [`StreamRef`] into a string of the given multibase.
+    fn to_string_of_base(&self, base: Base) -> String {
+        encode(base, self.to_bytes())
+    }
+
     /// Encode the [`StreamRef`] into a string.
     fn to_base36_string(&self) -> String {
-        encode(Base::Base36Lower, self.to_bytes())
+        self.to_string_of_base(Base::Base36Lower)
     }
 
     /// Encode the [`StreamRef`] into a base36 URL.
     fn to_url(&self) -> String {
         format!("ceramic://{}", self.to_base36_string())
     }
+
+    /// Encode the [`StreamRef`] into a legacy `/ceramic/<stream id>?commit=<cid>` URL, with
+    /// `commit=0` for the genesis commit.
+    fn to_legacy_url(&self) -> String {
+        let base = self.to_base_id();
+        let bytes = self.to_bytes();
+        match &bytes[base.encoded_len()..] {
+            [] => format!("/ceramic/{base}"),
+            // Zero commit
+            [0] => format!("/ceramic/{base}?commit=0"),
+            commit => {
+                let commit = Cid::try_from(commit).expect("the encoded commit is a CID");
+                format!("/ceramic/{base}?commit={commit}")
+            }
+        }
+    }
 }
 
 impl StreamRefExt for StreamRef {
@@ -82,6 +147,49 @@ impl StreamRefExt for StreamRef {
             StreamRef::CommitId(commit_id) => commit_id.to_bytes(),
         }
     }
+
+    fn encoded_len(&self) -> usize {
+        match self {
+            StreamRef::StreamId(stream_id) => stream_id.encoded_len(),
+            StreamRef::CommitId(commit_id) => commit_id.encoded_len(),
+        }
+    }
+
+    fn write_bytes<W: io::Write>(&self, w: W) -> Result<usize> {
+        match self {
+            StreamRef::StreamId(stream_id) => stream_id.write_bytes(w),
+            StreamRef::CommitId(commit_id) => commit_id.write_bytes(w),
+        }
+    }
+
+    fn to_legacy_url(&self) -> String {
+        match self {
+            StreamRef::StreamId(stream_id) => stream_id.to_legacy_url(),
+            StreamRef::CommitId(commit_id) => commit_id.to_legacy_url(),
+        }
+    }
+}
+
+impl PartialOrd for StreamRef {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for StreamRef {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (StreamRef::CommitId(commit_id), StreamRef::CommitId(other)) => commit_id.cmp(other),
+            _ => util::cmp_varint(self.stream_type().code(), other.stream_type().code())
+                .then_with(|| util::cmp_cid(self.cid(), other.cid()))
+                // A StreamId is a prefix of the bytes of the CommitIds of its stream
+                .then_with(|| {
+                    let is_commit_id =
+                        |stream_ref: &Self| matches!(stream_ref, StreamRef::CommitId(_));
+                    is_commit_id(self).cmp(&is_commit_id(other))
+                }),
+        }
+    }
 }
 
 impl fmt::Display for StreamRef {
@@ -94,6 +202,18 @@ impl FromStr for StreamRef {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        util::from_str::<true, true>(s)
+        Self::from_str_with(s, &ParseOptions::default())
+    }
+}
+
+impl Serialize for StreamRef {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        util::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for StreamRef {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        util::deserialize::<true, true, _>(deserializer)
     }
 }
@@ -0,0 +1,201 @@
+use alloc::format;
+use core::{cmp::Ordering, hash};
+
+use cid::Cid;
+use unsigned_varint::decode::{u64 as decode_u64, u8 as decode_u8};
+
+use crate::{util, *};
+
+/// A borrowed view over the bytes of a [`StreamId`] or [`CommitId`].
+///
+/// The layout is validated once when the view is created, accessors then return slices of the
+/// original bytes without copying. Views compare, order and hash by their bytes.
+///
+/// ```rust
+/// # use streamid::*;
+/// # let bytes = cid::multibase::decode("kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s").unwrap().1;
+/// let view = StreamRefView::from_slice(&bytes).unwrap();
+///
+/// assert_eq!(view.stream_type(), StreamType::Tile);
+/// assert!(!view.is_commit_id());
+/// assert_eq!(view.to_owned(), StreamRef::from_slice(&bytes).unwrap());
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct StreamRefView<'a> {
+    bytes: &'a [u8],
+    stream_type: StreamType,
+    cid_start: usize,
+    cid_end: usize,
+}
+
+impl<'a> StreamRefView<'a> {
+    /// Validate the layout of a slice of bytes.
+    pub fn from_slice(bytes: &'a [u8]) -> Result<Self> {
+        Self::from_slice_with(bytes, &ParseOptions::default())
+    }
+
+    /// Validate the layout of a slice of bytes with the given [`ParseOptions`].
+    ///
+    /// Bytes following the commit CID are not part of the view, unless
+    /// [`ParseOptions::strict`] rejects them.
+    pub fn from_slice_with(bytes: &'a [u8], options: &ParseOptions) -> Result<Self> {
+        Self::parse(bytes, options).map_err(|err| err.with_input(util::to_hex(bytes)).into())
+    }
+
+    fn parse(bytes: &'a [u8], options: &ParseOptions) -> Result<Self, ParseError> {
+        let offset = |buf: &[u8]| bytes.len() - buf.len();
+
+        let (stream_codec, buf) =
+            decode_u8(bytes).map_err(|err| ParseError::new(ParseStage::Codec, 0, "varint", err))?;
+        if stream_codec != STREAMID_CODEC {
+            return Err(ParseError::new(
+                ParseStage::Codec,
+                0,
+                format!("streamid codec {STREAMID_CODEC}"),
+                stream_codec,
+            ));
+        }
+
+        let type_offset = offset(buf);
+        let (stream_type, buf) = decode_u64(buf)
+            .map_err(|err| ParseError::new(ParseStage::StreamType, type_offset, "varint", err))?;
+        let stream_type = util::stream_type(stream_type, type_offset, options)?;
+
+        let cid_start = offset(buf);
+        let (cid, buf) = util::read_cid(buf)
+            .map_err(|err| ParseError::new(ParseStage::GenesisCid, cid_start, "CID", err))?;
+        let cid_end = offset(buf);
+
+        let end = match buf {
+            // StreamId or zero commit
+            [] | [0] => bytes.len(),
+            // Commit
+            _ => {
+                let (commit, rest) = util::read_cid(buf).map_err(|err| {
+                    ParseError::new(ParseStage::CommitCid, cid_end, "CID or zero commit", err)
+                })?;
+                if options.strict && commit == cid {
+                    return Err(ParseError::new(
+                        ParseStage::CommitCid,
+                        cid_end,
+                        "zero commit for genesis",
+                        "genesis CID",
+                    ));
+                }
+                offset(rest)
+            }
+        };
+
+        if options.strict && end != bytes.len() {
+            return Err(ParseError::new(
+                ParseStage::TrailingData,
+                end,
+                "end of input",
+                format!("{} bytes", bytes.len() - end),
+            ));
+        }
+
+        Ok(Self {
+            bytes: &bytes[..end],
+            stream_type,
+            cid_start,
+            cid_end,
+        })
+    }
+
+    /// Get the bytes of the [`StreamId`] or [`CommitId`].
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Get the [`StreamType`].
+    pub fn stream_type(&self) -> StreamType {
+        self.stream_type
+    }
+
+    /// Get the bytes of the genesis CID.
+    pub fn cid_bytes(&self) -> &'a [u8] {
+        &self.bytes[self.cid_start..self.cid_end]
+    }
+
+    // Offset of the commit CID, or of the end of a StreamId.
+    pub(crate) fn commit_offset(&self) -> usize {
+        self.cid_end
+    }
+
+    /// Whether the bytes are a [`CommitId`] rather than a [`StreamId`].
+    pub fn is_commit_id(&self) -> bool {
+        self.bytes.len() > self.cid_end
+    }
+
+    /// Get the bytes of the commit CID, [`None`] for a [`StreamId`] or a [`CommitId`] at the
+    /// genesis commit, matching [`CommitId::commit`](CommitId#structfield.commit).
+    pub fn commit_bytes(&self) -> Option<&'a [u8]> {
+        match &self.bytes[self.cid_end..] {
+            [] | [0] => None,
+            commit => Some(commit),
+        }
+    }
+
+    /// Get the owned [`StreamRef`].
+    pub fn to_owned(&self) -> StreamRef {
+        let cid = read_validated_cid(self.cid_bytes());
+
+        if self.is_commit_id() {
+            StreamRef::CommitId(CommitId {
+                stream_type: self.stream_type,
+                cid,
+                commit: self.commit_bytes().map(read_validated_cid),
+            })
+        } else {
+            StreamRef::StreamId(StreamId {
+                stream_type: self.stream_type,
+                cid,
+            })
+        }
+    }
+}
+
+fn read_validated_cid(bytes: &[u8]) -> Cid {
+    Cid::read_bytes(bytes).expect("CID bytes are validated when creating the view")
+}
+
+impl<'a> TryFrom<&'a [u8]> for StreamRefView<'a> {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self> {
+        Self::from_slice(bytes)
+    }
+}
+
+impl From<StreamRefView<'_>> for StreamRef {
+    fn from(view: StreamRefView<'_>) -> Self {
+        view.to_owned()
+    }
+}
+
+impl PartialEq for StreamRefView<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes == other.bytes
+    }
+}
+
+impl Eq for StreamRefView<'_> {}
+
+impl PartialOrd for StreamRefView<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for StreamRefView<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bytes.cmp(other.bytes)
+    }
+}
+
+impl hash::Hash for StreamRefView<'_> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.bytes.hash(state);
+    }
+}
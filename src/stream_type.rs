@@ -1,27 +1,280 @@
-use num_enum::{IntoPrimitive, TryFromPrimitive};
-use serde::{Deserialize, Serialize};
-use serde_plain::{derive_display_from_serialize, derive_fromstr_from_deserialize};
-
-#[derive(
-    Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize, IntoPrimitive, TryFromPrimitive,
-)]
-#[repr(u8)]
+use core::{fmt, str::FromStr};
+
+use num_enum::{TryFromPrimitive, TryFromPrimitiveError};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::*;
+
+/// Type of a stream, encoded as a varint code in [`StreamId`] and [`CommitId`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum StreamType {
-    #[serde(rename = "tile")]
-    Tile = 0,
+    Tile,
+    Caip10Link,
+    Model,
+    Mid,
+    Unloadable,
+
+    /// A stream type code not known to this crate, only produced by [`StreamType::from_code`]
+    /// or when parsing with [`ParseOptions::allow_unknown_stream_types`].
+    Unknown(UnknownStreamType),
+}
+
+/// Code of a stream type not known to this crate, never the code of a known type.
+///
+/// ```rust
+/// # use streamid::*;
+/// let StreamType::Unknown(unknown) = StreamType::from_code(300) else { panic!() };
+/// assert_eq!(unknown.code(), 300);
+/// assert_eq!(StreamType::from_code(3), StreamType::Mid);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct UnknownStreamType(u64);
+
+impl UnknownStreamType {
+    /// Get the code.
+    pub fn code(&self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Display for UnknownStreamType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl StreamType {
+    /// Get the [`StreamType`] for a code, keeping unknown codes as [`StreamType::Unknown`].
+    pub fn from_code(code: u64) -> Self {
+        match code {
+            0 => StreamType::Tile,
+            1 => StreamType::Caip10Link,
+            2 => StreamType::Model,
+            3 => StreamType::Mid,
+            4 => StreamType::Unloadable,
+            code => StreamType::Unknown(UnknownStreamType(code)),
+        }
+    }
+
+    /// Get the code of the [`StreamType`].
+    pub fn code(&self) -> u64 {
+        match self {
+            StreamType::Tile => 0,
+            StreamType::Caip10Link => 1,
+            StreamType::Model => 2,
+            StreamType::Mid => 3,
+            StreamType::Unloadable => 4,
+            StreamType::Unknown(unknown) => unknown.code(),
+        }
+    }
+
+    /// Get the name of the [`StreamType`], [`None`] for unknown types.
+    pub fn name(&self) -> Option<&'static str> {
+        match self {
+            StreamType::Tile => Some("tile"),
+            StreamType::Caip10Link => Some("caip10-link"),
+            StreamType::Model => Some("model"),
+            StreamType::Mid => Some("MID"),
+            StreamType::Unloadable => Some("UNLOADABLE"),
+            StreamType::Unknown(_) => None,
+        }
+    }
+
+    /// Whether the [`StreamType`] is known to this crate.
+    pub fn is_known(&self) -> bool {
+        !matches!(self, StreamType::Unknown(_))
+    }
+}
+
+impl From<StreamType> for u64 {
+    fn from(stream_type: StreamType) -> Self {
+        stream_type.code()
+    }
+}
+
+impl TryFrom<u64> for StreamType {
+    type Error = Error;
+
+    fn try_from(code: u64) -> Result<Self> {
+        match StreamType::from_code(code) {
+            StreamType::Unknown(unknown) => Err(Error::InvalidStreamTypeIndex(unknown.code())),
+            stream_type => Ok(stream_type),
+        }
+    }
+}
+
+impl TryFromPrimitive for StreamType {
+    type Primitive = u8;
+
+    const NAME: &'static str = "StreamType";
+
+    fn try_from_primitive(code: u8) -> Result<Self, TryFromPrimitiveError<Self>> {
+        match StreamType::from_code(code.into()) {
+            StreamType::Unknown(_) => Err(TryFromPrimitiveError { number: code }),
+            stream_type => Ok(stream_type),
+        }
+    }
+}
+
+impl TryFrom<u8> for StreamType {
+    type Error = TryFromPrimitiveError<Self>;
+
+    fn try_from(code: u8) -> Result<Self, Self::Error> {
+        StreamType::try_from_primitive(code)
+    }
+}
+
+// Unknown codes may not fit in a `u8`, so unlike `u64` this conversion can fail.
+impl TryFrom<StreamType> for u8 {
+    type Error = Error;
+
+    fn try_from(stream_type: StreamType) -> Result<Self> {
+        u8::try_from(stream_type.code())
+            .map_err(|_| Error::InvalidStreamTypeIndex(stream_type.code()))
+    }
+}
+
+// Known types display as their name, unknown types as their code.
+impl fmt::Display for StreamType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name() {
+            Some(name) => f.write_str(name),
+            None => write!(f, "{}", self.code()),
+        }
+    }
+}
+
+impl FromStr for StreamType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "tile" => Ok(StreamType::Tile),
+            "caip10-link" => Ok(StreamType::Caip10Link),
+            "model" => Ok(StreamType::Model),
+            "MID" => Ok(StreamType::Mid),
+            "UNLOADABLE" => Ok(StreamType::Unloadable),
+            s => s
+                .parse()
+                .map(StreamType::from_code)
+                .map_err(|_| Error::InvalidStreamTypeName(s.into())),
+        }
+    }
+}
+
+// Known types are written as the unit variants `#[derive(Serialize)]` produced before
+// `Unknown` existed, so existing data still reads. Unknown types are their code in
+// human-readable formats and an `Unknown` newtype variant holding it in binary formats.
+const VARIANTS: &[&str] = &[
+    "tile",
+    "caip10-link",
+    "model",
+    "MID",
+    "UNLOADABLE",
+    "Unknown",
+];
+const UNKNOWN_INDEX: u32 = 5;
+
+impl Serialize for StreamType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.name() {
+            Some(name) => serializer.serialize_unit_variant("StreamType", self.code() as u32, name),
+            None if serializer.is_human_readable() => serializer.serialize_u64(self.code()),
+            None => serializer.serialize_newtype_variant(
+                "StreamType",
+                UNKNOWN_INDEX,
+                "Unknown",
+                &self.code(),
+            ),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for StreamType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(StreamTypeVisitor)
+        } else {
+            deserializer.deserialize_enum("StreamType", VARIANTS, StreamTypeVisitor)
+        }
+    }
+}
+
+struct StreamTypeVisitor;
+
+impl<'de> de::Visitor<'de> for StreamTypeVisitor {
+    type Value = StreamType;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a stream type name or code")
+    }
 
-    #[serde(rename = "caip10-link")]
-    Caip10Link = 1,
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        StreamType::from_str(v).map_err(E::custom)
+    }
 
-    #[serde(rename = "model")]
-    Model = 2,
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(StreamType::from_code(v))
+    }
 
-    #[serde(rename = "MID")]
-    Mid = 3,
+    fn visit_enum<A: de::EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        match data.variant()? {
+            (Variant::Known(stream_type), variant) => {
+                de::VariantAccess::unit_variant(variant)?;
+                Ok(stream_type)
+            }
+            (Variant::Unknown, variant) => {
+                de::VariantAccess::newtype_variant(variant).map(StreamType::from_code)
+            }
+        }
+    }
+}
+
+enum Variant {
+    Known(StreamType),
+    Unknown,
+}
 
-    #[serde(rename = "UNLOADABLE")]
-    Unloadable = 4,
+impl<'de> Deserialize<'de> for Variant {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_identifier(VariantVisitor)
+    }
 }
 
-derive_display_from_serialize!(StreamType);
-derive_fromstr_from_deserialize!(StreamType);
+struct VariantVisitor;
+
+impl<'de> de::Visitor<'de> for VariantVisitor {
+    type Value = Variant;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a StreamType variant")
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        match StreamType::from_code(v) {
+            _ if v == u64::from(UNKNOWN_INDEX) => Ok(Variant::Unknown),
+            StreamType::Unknown(_) => Err(E::invalid_value(
+                de::Unexpected::Unsigned(v),
+                &"variant index 0 <= i < 6",
+            )),
+            stream_type => Ok(Variant::Known(stream_type)),
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        match v {
+            "Unknown" => Ok(Variant::Unknown),
+            v => match StreamType::from_str(v) {
+                Ok(stream_type) if stream_type.is_known() => Ok(Variant::Known(stream_type)),
+                _ => Err(E::unknown_variant(v, VARIANTS)),
+            },
+        }
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        match core::str::from_utf8(v) {
+            Ok(v) => self.visit_str(v),
+            Err(_) => Err(E::invalid_value(de::Unexpected::Bytes(v), &self)),
+        }
+    }
+}
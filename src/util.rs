@@ -1,53 +1,107 @@
-use std::{io, str::FromStr};
+use alloc::{format, string::String};
+use core::{cmp::Ordering, fmt, str::FromStr};
 
 use cid::{
     multibase::{decode, encode, Base},
-    Cid,
+    Cid, Version,
 };
+#[cfg(feature = "regex")]
 use once_cell::sync::Lazy;
+#[cfg(feature = "regex")]
 use regex::Regex;
-use unsigned_varint::decode::u8 as decode_u8;
+use serde::{de, Deserializer, Serializer};
+use unsigned_varint::{decode as varint_decode, encode as varint_encode};
 
-use crate::*;
+use crate::{io, *};
 
 // RegExp to match against URL representation of StreamID.
+#[cfg(feature = "regex")]
 static URL_PATTERN: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"^(?:ceramic://|/ceramic/)?([a-zA-Z0-9]+)$").unwrap());
+    Lazy::new(|| Regex::new(r"^(?:ceramic://|/ceramic/)?([a-zA-Z0-9_-]+)$").unwrap());
 
 // RegExp to match against URL representation of StreamID CommitID.
+#[cfg(feature = "regex")]
 static URL_PATTERN_COMMIT: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"^(?:ceramic://|/ceramic/)?([a-zA-Z0-9]+)(?:\?commit=([a-zA-Z0-9]+))?$").unwrap()
+    Regex::new(r"^(?:ceramic://|/ceramic/)?([a-zA-Z0-9_-]+)(?:\?commit=([a-zA-Z0-9_-]+))?$")
+        .unwrap()
 });
 
-pub fn from_str<const COMMIT_ID: bool, const STREAM_ID: bool>(s: &str) -> Result<StreamRef> {
-    let err = || {
-        if COMMIT_ID && STREAM_ID {
-            Error::InvalidStreamRefString(s.into())
-        } else if COMMIT_ID {
-            Error::InvalidCommitIdString(s.into())
+// Multibase encodings accepted when parsing strings.
+const BASES: [Base; 5] = [
+    Base::Base36Lower,
+    Base::Base32Lower,
+    Base::Base58Btc,
+    Base::Base64Url,
+    Base::Base16Lower,
+];
+
+pub fn from_str<const COMMIT_ID: bool, const STREAM_ID: bool>(
+    s: &str,
+    options: &ParseOptions,
+) -> Result<StreamRef> {
+    let err = |err: ParseError| Error::from(err.with_input(s));
+
+    let ((encoded_start, encoded), commit) =
+        split_url::<COMMIT_ID>(s).ok_or_else(|| err(url_error(s, COMMIT_ID)))?;
+
+    let (base, bytes) = decode(encoded).map_err(|e| {
+        err(ParseError::new(
+            ParseStage::Multibase,
+            encoded_start,
+            "multibase string",
+            e,
+        ))
+    })?;
+    if !is_accepted_base(base, options) {
+        let expected = if options.base36_only || options.strict {
+            "base36"
         } else {
-            Error::InvalidStreamIdString(s.into())
+            "base36, base32, base58btc, base64url or base16"
+        };
+        return Err(err(ParseError::new(
+            ParseStage::Multibase,
+            encoded_start,
+            expected,
+            format!("{base:?}"),
+        )));
+    }
+    if options.strict {
+        if encoded != s {
+            return Err(err(ParseError::new(
+                ParseStage::Url,
+                0,
+                "bare multibase string",
+                "URL",
+            )));
+        }
+        if encode(base, &bytes) != encoded {
+            return Err(err(ParseError::new(
+                ParseStage::Multibase,
+                encoded_start,
+                "canonical encoding",
+                "non-canonical encoding",
+            )));
         }
-    };
-
-    let protocol_match = if COMMIT_ID {
-        &*URL_PATTERN_COMMIT
-    } else {
-        &*URL_PATTERN
     }
-    .captures(s)
-    .ok_or_else(err)?;
 
-    let base = protocol_match.get(1).map(|m| m.as_str()).ok_or_else(err)?;
-    let (_, bytes) = decode(base).map_err(|_| err())?;
+    let mut stream_ref = try_from_slice::<true, true>(&bytes, options).map_err(|e| match e {
+        Error::Parse(e) => err(e),
+        e => e,
+    })?;
 
-    let mut stream_ref = StreamRef::from_slice(bytes)?;
-    let commit = protocol_match.get(2);
-
-    if COMMIT_ID && commit.is_some() {
-        let commit = commit
-            .map(|m| m.as_str())
-            .and_then(|commit| Cid::from_str(commit).ok());
+    if let Some((commit_start, commit)) = commit {
+        let commit = match commit {
+            // Zero commit
+            "0" => None,
+            cid => Some(Cid::from_str(cid).map_err(|e| {
+                err(ParseError::new(
+                    ParseStage::CommitCid,
+                    commit_start,
+                    "CID or 0",
+                    e,
+                ))
+            })?),
+        };
 
         stream_ref = match stream_ref {
             StreamRef::CommitId(CommitId {
@@ -68,50 +122,285 @@ pub fn from_str<const COMMIT_ID: bool, const STREAM_ID: bool>(s: &str) -> Result
     match stream_ref {
         StreamRef::CommitId(_) if COMMIT_ID => {}
         StreamRef::StreamId(_) if STREAM_ID => {}
-        _ => return Err(err()),
+        StreamRef::CommitId(_) => {
+            return Err(err(ParseError::new(
+                ParseStage::Kind,
+                encoded_start,
+                "StreamID",
+                "CommitID",
+            )))
+        }
+        StreamRef::StreamId(_) => {
+            return Err(err(ParseError::new(
+                ParseStage::Kind,
+                encoded_start,
+                "CommitID",
+                "StreamID",
+            )))
+        }
     }
 
     Ok(stream_ref)
 }
 
+type Span<'a> = (usize, &'a str);
+
+// Splits a string or URL into the offset and string of the StreamRef and of the commit query.
+#[cfg(feature = "regex")]
+fn split_url<'a, const COMMIT_ID: bool>(s: &'a str) -> Option<(Span<'a>, Option<Span<'a>>)> {
+    let captures = if COMMIT_ID {
+        &URL_PATTERN_COMMIT
+    } else {
+        &URL_PATTERN
+    }
+    .captures(s)?;
+    let span = |m: regex::Match<'a>| (m.start(), m.as_str());
+
+    Some((span(captures.get(1)?), captures.get(2).map(span)))
+}
+
+// Without the URL patterns only bare multibase strings are accepted.
+#[cfg(not(feature = "regex"))]
+fn split_url<const COMMIT_ID: bool>(s: &str) -> Option<(Span<'_>, Option<Span<'_>>)> {
+    (!s.is_empty() && s.chars().all(is_id_char)).then_some(((0, s), None))
+}
+
+// Gets the multibase string of a StreamRef string or URL.
+pub fn encoded(s: &str) -> Option<&str> {
+    split_url::<true>(s).map(|((_, encoded), _)| encoded)
+}
+
+fn is_id_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_'
+}
+
+// Locates the first character rejected by the URL patterns.
+fn url_error(s: &str, commit: bool) -> ParseError {
+    let skip_id = |offset: usize| {
+        s[offset..]
+            .find(|c| !is_id_char(c))
+            .map_or(s.len(), |len| offset + len)
+    };
+
+    let prefix = ["ceramic://", "/ceramic/"]
+        .iter()
+        .find(|prefix| s.starts_with(*prefix))
+        .map_or(0, |prefix| prefix.len());
+    if !cfg!(feature = "regex") && prefix > 0 {
+        return ParseError::new(ParseStage::Url, 0, "bare multibase string", "URL");
+    }
+
+    let mut offset = skip_id(prefix);
+    if offset == prefix {
+        return ParseError::new(
+            ParseStage::Url,
+            offset,
+            "multibase string",
+            s[offset..]
+                .chars()
+                .next()
+                .map_or("end of input".into(), |c| format!("{c:?}")),
+        );
+    }
+    if cfg!(feature = "regex") && commit && s[offset..].starts_with("?commit=") {
+        offset = skip_id(offset + "?commit=".len());
+    }
+
+    match s[offset..].chars().next() {
+        Some(c) => ParseError::new(ParseStage::Url, offset, "end of input", format!("{c:?}")),
+        None => ParseError::new(ParseStage::Url, offset, "commit CID", "end of input"),
+    }
+}
+
 pub fn try_from_slice<const COMMIT_ID: bool, const STREAM_ID: bool>(
     buf: &[u8],
+    options: &ParseOptions,
 ) -> Result<StreamRef> {
-    let (stream_codec, buf) = decode_u8(buf)?;
-    if stream_codec != STREAMID_CODEC {
-        return Err(Error::InvalidStreamRefCodec);
-    }
-
-    let (stream_type, buf) = decode_u8(buf)?;
-    let stream_type = StreamType::try_from(stream_type)?;
-
-    let (cid, buf) = read_cid(buf)?;
-
-    if STREAM_ID && buf.is_empty() {
-        Ok(StreamRef::StreamId(StreamId { stream_type, cid }))
-    } else if !COMMIT_ID {
-        Err(Error::InvalidStreamIdBytes(encode(Base::Base36Lower, buf)))
-    } else if buf.len() == 1 && buf[0] == 0 {
-        // Zero commit
-        Ok(StreamRef::CommitId(CommitId {
-            stream_type,
-            cid,
-            commit: None,
-        }))
+    let view = StreamRefView::from_slice_with(buf, options)?;
+
+    let (expected, actual) = match view.is_commit_id() {
+        false if STREAM_ID => return Ok(view.to_owned()),
+        true if COMMIT_ID => return Ok(view.to_owned()),
+        false => ("CommitID", "StreamID"),
+        true => ("StreamID", "CommitID"),
+    };
+
+    Err(
+        ParseError::new(ParseStage::Kind, view.commit_offset(), expected, actual)
+            .with_input(to_hex(buf))
+            .into(),
+    )
+}
+
+pub fn is_accepted_base(base: Base, options: &ParseOptions) -> bool {
+    if options.base36_only || options.strict {
+        base == Base::Base36Lower
     } else {
-        // Commit
-        let commit = Cid::read_bytes(buf)?;
-        Ok(StreamRef::CommitId(CommitId {
-            stream_type,
-            cid,
-            commit: Some(commit),
-        }))
+        BASES.contains(&base)
+    }
+}
+
+pub fn stream_type(
+    code: u64,
+    offset: usize,
+    options: &ParseOptions,
+) -> Result<StreamType, ParseError> {
+    match StreamType::from_code(code) {
+        StreamType::Unknown(_) if !options.allow_unknown_stream_types => Err(ParseError::new(
+            ParseStage::StreamType,
+            offset,
+            "known stream type",
+            code,
+        )),
+        stream_type => Ok(stream_type),
     }
 }
 
+// Reads the codec and stream type preceding the genesis CID.
+pub fn read_header<R: io::Read>(mut r: R, options: &ParseOptions) -> Result<StreamType> {
+    let (stream_codec, offset) = read_varint(&mut r)?;
+    if stream_codec != u64::from(STREAMID_CODEC) {
+        return Err(ParseError::new(
+            ParseStage::Codec,
+            0,
+            format!("streamid codec {STREAMID_CODEC}"),
+            stream_codec,
+        )
+        .into());
+    }
+
+    let (stream_type_code, _) = read_varint(&mut r)?;
+    Ok(stream_type(stream_type_code, offset, options)?)
+}
+
+// Reads an unsigned varint, returns its value and length.
+fn read_varint<R: io::Read>(mut r: R) -> Result<(u64, usize)> {
+    let mut buf = varint_encode::u64_buffer();
+    for len in 1..=buf.len() {
+        r.read_exact(&mut buf[len - 1..len])?;
+        if varint_decode::is_last(buf[len - 1]) {
+            let (value, _) = varint_decode::u64(&buf[..len])?;
+            return Ok((value, len));
+        }
+    }
+
+    Err(varint_decode::Error::Overflow.into())
+}
+
 pub fn read_cid(bytes: &[u8]) -> Result<(Cid, &[u8])> {
-    let mut reader = io::Cursor::new(bytes);
+    let mut reader = bytes;
     let cid = Cid::read_bytes(&mut reader)?;
-    let bytes = &bytes[reader.position() as usize..];
-    Ok((cid, bytes))
+    Ok((cid, reader))
+}
+
+/// Compare varints in the order of their encoded bytes.
+pub fn cmp_varint(a: u64, b: u64) -> Ordering {
+    let (mut a_buf, mut b_buf) = (varint_encode::u64_buffer(), varint_encode::u64_buffer());
+    varint_encode::u64(a, &mut a_buf).cmp(varint_encode::u64(b, &mut b_buf))
+}
+
+/// Compare CIDs in the order of their encoded bytes, without encoding the digests.
+pub fn cmp_cid(a: &Cid, b: &Cid) -> Ordering {
+    let (mut a_buf, mut b_buf) = ([0; 40], [0; 40]);
+    let a_bytes = cid_prefix(a, &mut a_buf).iter().chain(a.hash().digest());
+    let b_bytes = cid_prefix(b, &mut b_buf).iter().chain(b.hash().digest());
+    a_bytes.cmp(b_bytes)
+}
+
+// Encodes the bytes of a CID before its digest: the version and codec unless CIDv0, then the
+// multihash code and digest size.
+fn cid_prefix<'a>(cid: &Cid, buf: &'a mut [u8; 40]) -> &'a [u8] {
+    let mut varints = [None; 4];
+    if cid.version() != Version::V0 {
+        varints[0] = Some(u64::from(cid.version()));
+        varints[1] = Some(cid.codec());
+    }
+    varints[2] = Some(cid.hash().code());
+    varints[3] = Some(cid.hash().size().into());
+
+    let mut len = 0;
+    for value in varints.into_iter().flatten() {
+        let mut varint_buf = varint_encode::u64_buffer();
+        let varint = varint_encode::u64(value, &mut varint_buf);
+        buf[len..len + varint.len()].copy_from_slice(varint);
+        len += varint.len();
+    }
+    &buf[..len]
+}
+
+/// Whether a URL path segment is `.` or `..`, with any of the dots percent-encoded as `%2e` or
+/// `%2E` as URL parsers decode them.
+pub fn is_dot_segment(segment: &str) -> bool {
+    let mut rest = segment;
+    let mut dots = 0;
+    while !rest.is_empty() {
+        rest = match rest.strip_prefix('.') {
+            Some(rest) => rest,
+            None if rest
+                .get(..3)
+                .map_or(false, |s| s.eq_ignore_ascii_case("%2e")) =>
+            {
+                &rest[3..]
+            }
+            None => return false,
+        };
+        dots += 1;
+    }
+    matches!(dots, 1 | 2)
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+pub fn serialize<T, S>(stream_ref: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: StreamRefExt + fmt::Display,
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        serializer.collect_str(stream_ref)
+    } else {
+        serializer.serialize_bytes(&stream_ref.to_bytes())
+    }
+}
+
+pub fn deserialize<'de, const COMMIT_ID: bool, const STREAM_ID: bool, D>(
+    deserializer: D,
+) -> Result<StreamRef, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(Visitor::<COMMIT_ID, STREAM_ID>)
+    } else {
+        deserializer.deserialize_bytes(Visitor::<COMMIT_ID, STREAM_ID>)
+    }
+}
+
+// Accepts the string forms handled by `from_str` and the bytes handled by `try_from_slice`.
+struct Visitor<const COMMIT_ID: bool, const STREAM_ID: bool>;
+
+impl<'de, const COMMIT_ID: bool, const STREAM_ID: bool> de::Visitor<'de>
+    for Visitor<COMMIT_ID, STREAM_ID>
+{
+    type Value = StreamRef;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if COMMIT_ID && STREAM_ID {
+            f.write_str("a StreamID or CommitID")
+        } else if COMMIT_ID {
+            f.write_str("a CommitID")
+        } else {
+            f.write_str("a StreamID")
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        from_str::<COMMIT_ID, STREAM_ID>(v, &ParseOptions::default()).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        try_from_slice::<COMMIT_ID, STREAM_ID>(v, &ParseOptions::default()).map_err(E::custom)
+    }
 }
@@ -0,0 +1,208 @@
+//! WebAssembly bindings mirroring the JS `@ceramicnetwork/streamid` classes.
+//!
+//! CIDs are passed to and from JS as strings, and a commit of `"0"` refers to the genesis commit.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::str::FromStr;
+
+use cid::Cid;
+use wasm_bindgen::prelude::*;
+
+use crate::*;
+
+/// JS `StreamID`, see [`StreamId`].
+#[wasm_bindgen(js_name = StreamID)]
+pub struct JsStreamId(StreamId);
+
+#[wasm_bindgen(js_class = StreamID)]
+impl JsStreamId {
+    /// Parse from a string or URL.
+    #[wasm_bindgen(js_name = fromString)]
+    pub fn from_string(s: &str) -> Result<JsStreamId, JsError> {
+        StreamId::from_str(s).map(Self).map_err(js_error)
+    }
+
+    /// Parse from bytes.
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<JsStreamId, JsError> {
+        StreamId::from_slice(bytes).map(Self).map_err(js_error)
+    }
+
+    /// Stream type code.
+    #[wasm_bindgen(getter, js_name = type)]
+    pub fn stream_type(&self) -> f64 {
+        self.0.stream_type().code() as f64
+    }
+
+    /// Stream type name, the code for unknown types.
+    #[wasm_bindgen(getter, js_name = typeName)]
+    pub fn type_name(&self) -> String {
+        self.0.stream_type().to_string()
+    }
+
+    /// Genesis CID.
+    #[wasm_bindgen(getter)]
+    pub fn cid(&self) -> String {
+        self.0.cid().to_string()
+    }
+
+    /// Encoded bytes.
+    #[wasm_bindgen(getter)]
+    pub fn bytes(&self) -> Vec<u8> {
+        self.0.to_bytes()
+    }
+
+    /// The [`StreamId`] itself, for parity with `CommitID`.
+    #[wasm_bindgen(getter, js_name = baseID)]
+    pub fn base_id(&self) -> JsStreamId {
+        JsStreamId(self.0.to_base_id())
+    }
+
+    /// Create a `CommitID` at the given commit CID, or `"0"` for the genesis commit.
+    #[wasm_bindgen(js_name = atCommit)]
+    pub fn at_commit(&self, commit: &str) -> Result<JsCommitId, JsError> {
+        at_commit(&self.0, commit)
+    }
+
+    /// Whether both refer to the same stream.
+    pub fn equals(&self, other: &JsStreamId) -> bool {
+        self.0 == other.0
+    }
+
+    /// Encode into a base36 string.
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_base36_string(&self) -> String {
+        self.0.to_base36_string()
+    }
+
+    /// Encode into a base36 URL.
+    #[wasm_bindgen(js_name = toUrl)]
+    pub fn to_url(&self) -> String {
+        self.0.to_url()
+    }
+}
+
+/// JS `CommitID`, see [`CommitId`].
+#[wasm_bindgen(js_name = CommitID)]
+pub struct JsCommitId(CommitId);
+
+#[wasm_bindgen(js_class = CommitID)]
+impl JsCommitId {
+    /// Parse from a string or URL.
+    #[wasm_bindgen(js_name = fromString)]
+    pub fn from_string(s: &str) -> Result<JsCommitId, JsError> {
+        CommitId::from_str(s).map(Self).map_err(js_error)
+    }
+
+    /// Parse from bytes.
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<JsCommitId, JsError> {
+        CommitId::from_slice(bytes).map(Self).map_err(js_error)
+    }
+
+    /// Stream type code.
+    #[wasm_bindgen(getter, js_name = type)]
+    pub fn stream_type(&self) -> f64 {
+        self.0.stream_type().code() as f64
+    }
+
+    /// Stream type name, the code for unknown types.
+    #[wasm_bindgen(getter, js_name = typeName)]
+    pub fn type_name(&self) -> String {
+        self.0.stream_type().to_string()
+    }
+
+    /// Genesis CID.
+    #[wasm_bindgen(getter)]
+    pub fn cid(&self) -> String {
+        self.0.cid().to_string()
+    }
+
+    /// Commit CID, the genesis CID for the genesis commit.
+    #[wasm_bindgen(getter)]
+    pub fn commit(&self) -> String {
+        self.0.commit().to_string()
+    }
+
+    /// Encoded bytes.
+    #[wasm_bindgen(getter)]
+    pub fn bytes(&self) -> Vec<u8> {
+        self.0.to_bytes()
+    }
+
+    /// The `StreamID` of the stream.
+    #[wasm_bindgen(getter, js_name = baseID)]
+    pub fn base_id(&self) -> JsStreamId {
+        JsStreamId(self.0.to_base_id())
+    }
+
+    /// Create a `CommitID` of the same stream at the given commit CID, or `"0"` for the genesis
+    /// commit.
+    #[wasm_bindgen(js_name = atCommit)]
+    pub fn at_commit(&self, commit: &str) -> Result<JsCommitId, JsError> {
+        at_commit(&self.0, commit)
+    }
+
+    /// Whether both refer to the same commit, an explicit genesis commit equals `"0"`.
+    pub fn equals(&self, other: &JsCommitId) -> bool {
+        self.0.canonical() == other.0.canonical()
+    }
+
+    /// Encode into a base36 string.
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_base36_string(&self) -> String {
+        self.0.to_base36_string()
+    }
+
+    /// Encode into a base36 URL.
+    #[wasm_bindgen(js_name = toUrl)]
+    pub fn to_url(&self) -> String {
+        self.0.to_url()
+    }
+}
+
+/// JS `StreamRef`, parsing either a `StreamID` or a `CommitID`, see [`StreamRef`].
+#[wasm_bindgen(js_name = StreamRef)]
+pub struct JsStreamRef {}
+
+#[wasm_bindgen(js_class = StreamRef)]
+impl JsStreamRef {
+    /// Parse a `StreamID` or `CommitID` from a string or URL.
+    #[wasm_bindgen(js_name = fromString)]
+    pub fn from_string(s: &str) -> Result<JsValue, JsError> {
+        StreamRef::from_str(s).map(js_value).map_err(js_error)
+    }
+
+    /// Parse a `StreamID` or `CommitID` from bytes.
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<JsValue, JsError> {
+        StreamRef::from_slice(bytes).map(js_value).map_err(js_error)
+    }
+}
+
+fn at_commit<T: StreamRefExt>(stream_ref: &T, commit: &str) -> Result<JsCommitId, JsError> {
+    let commit_id = match commit {
+        // Genesis commit
+        "0" => CommitId {
+            commit: None,
+            ..stream_ref.at_commit(*stream_ref.cid())
+        },
+        commit => stream_ref.at_commit(Cid::from_str(commit).map_err(|err| js_error(err.into()))?),
+    };
+
+    Ok(JsCommitId(commit_id))
+}
+
+fn js_value(stream_ref: StreamRef) -> JsValue {
+    match stream_ref {
+        StreamRef::StreamId(stream_id) => JsStreamId(stream_id).into(),
+        StreamRef::CommitId(commit_id) => JsCommitId(commit_id).into(),
+    }
+}
+
+fn js_error(err: Error) -> JsError {
+    JsError::new(&err.to_string())
+}
@@ -0,0 +1,175 @@
+use std::str::FromStr;
+
+use streamid::*;
+
+const STREAM_ID_STRING: &str = "kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s";
+const STREAM_ID_WITH_COMMIT: &str =
+  "k1dpgaqe3i64kjqcp801r3sn7ysi5i0k7nxvs7j351s7kewfzr3l7mdxnj7szwo4kr9mn2qki5nnj0cv836ythy1t1gya9s25cn1nexst3jxi5o3h6qprfyju";
+const STREAM_ID_WITH_0_COMMIT: &str =
+    "k3y52l7qbv1frxwipl4hp7e6jlu4f6u8upm2xv0irmedfkm5cnutmezzi3u7mytj4";
+const COMMIT_CID_STRING: &str = "bagjqcgzaday6dzalvmy5ady2m5a5legq5zrbsnlxfc2bfxej532ds7htpova";
+
+#[test]
+fn parse() {
+    let url = CeramicUrl::from_str(&format!("ceramic://{STREAM_ID_STRING}")).unwrap();
+    assert_eq!(
+        url.stream_ref,
+        StreamRef::from_str(STREAM_ID_STRING).unwrap()
+    );
+    assert_eq!(url.path, "");
+    assert!(url.query.is_empty());
+
+    let url = CeramicUrl::from_str(&format!(
+        "ceramic://{STREAM_ID_STRING}/a/b%20c/?sort=asc&limit=10&flag"
+    ))
+    .unwrap();
+    assert_eq!(url.path, "/a/b%20c/");
+    assert_eq!(
+        url.query,
+        [
+            ("sort".to_string(), "asc".to_string()),
+            ("limit".to_string(), "10".to_string()),
+            ("flag".to_string(), String::new()),
+        ]
+    );
+    assert_eq!(url.param("limit"), Some("10"));
+    assert_eq!(url.param("commit"), None);
+
+    let url = CeramicUrl::from_str(&format!("ceramic://{STREAM_ID_WITH_COMMIT}/")).unwrap();
+    assert_eq!(
+        url.stream_ref,
+        StreamRef::from_str(STREAM_ID_WITH_COMMIT).unwrap()
+    );
+    assert_eq!(url.path, "/");
+}
+
+#[test]
+fn commit_query() {
+    let commit_id = StreamRef::from_str(STREAM_ID_WITH_COMMIT).unwrap();
+    for query in [
+        format!("commit={COMMIT_CID_STRING}"),
+        format!("version={COMMIT_CID_STRING}"),
+        format!("commit={COMMIT_CID_STRING}&version={COMMIT_CID_STRING}"),
+    ] {
+        let url = CeramicUrl::from_str(&format!("ceramic://{STREAM_ID_STRING}?{query}")).unwrap();
+        assert_eq!(url.stream_ref, commit_id);
+        assert!(url.query.is_empty());
+        assert_eq!(url.to_string(), commit_id.to_url());
+    }
+
+    let url =
+        CeramicUrl::from_str(&format!("ceramic://{STREAM_ID_STRING}?a=1&commit=0&b=2")).unwrap();
+    assert_eq!(
+        url.stream_ref,
+        StreamRef::from_str(STREAM_ID_WITH_0_COMMIT).unwrap()
+    );
+    assert_eq!(
+        url.to_string(),
+        format!("ceramic://{STREAM_ID_WITH_0_COMMIT}?a=1&b=2")
+    );
+
+    // The query selects another commit of the same stream.
+    let url = CeramicUrl::from_str(&format!("ceramic://{STREAM_ID_WITH_COMMIT}?commit=0")).unwrap();
+    assert_eq!(
+        url.stream_ref,
+        StreamRef::from_str(STREAM_ID_WITH_0_COMMIT).unwrap()
+    );
+}
+
+#[test]
+fn round_trip() {
+    for s in [
+        format!("ceramic://{STREAM_ID_STRING}"),
+        format!("ceramic://{STREAM_ID_WITH_COMMIT}"),
+        format!("ceramic://{STREAM_ID_WITH_0_COMMIT}/"),
+        format!("ceramic://{STREAM_ID_STRING}/content/title"),
+        format!("ceramic://{STREAM_ID_STRING}?a=1&b=%26&a=2"),
+        format!("ceramic://{STREAM_ID_WITH_COMMIT}/x?=y&z="),
+    ] {
+        let url = CeramicUrl::from_str(&s).unwrap();
+        assert_eq!(url.to_string(), s);
+        assert_eq!(CeramicUrl::from_str(&url.to_string()).unwrap(), url);
+    }
+
+    // Non-canonical URLs display differently but parse back equal.
+    for s in [
+        format!("ceramic://{STREAM_ID_STRING}?commit={COMMIT_CID_STRING}&flag"),
+        format!("ceramic://{STREAM_ID_STRING}?&&a=1&"),
+        format!(
+            "ceramic://{}/path",
+            StreamRef::from_str(STREAM_ID_STRING)
+                .unwrap()
+                .to_string_of_base(cid::multibase::Base::Base58Btc)
+        ),
+    ] {
+        let url = CeramicUrl::from_str(&s).unwrap();
+        assert_ne!(url.to_string(), s);
+        assert_eq!(CeramicUrl::from_str(&url.to_string()).unwrap(), url);
+    }
+
+    let url = CeramicUrl::from(StreamId::from_str(STREAM_ID_STRING).unwrap());
+    assert_eq!(url.to_string(), format!("ceramic://{STREAM_ID_STRING}"));
+}
+
+#[test]
+fn invalid() {
+    let parse_error = |s: String| match CeramicUrl::from_str(&s).unwrap_err() {
+        Error::Parse(err) => err,
+        err => panic!("unexpected error {err}"),
+    };
+
+    let err = parse_error(format!("/ceramic/{STREAM_ID_STRING}"));
+    assert_eq!((err.stage, err.offset), (ParseStage::Url, 0));
+
+    let err = parse_error(format!("ceramic://{STREAM_ID_STRING}#fragment"));
+    assert_eq!(err.stage, ParseStage::Url);
+    assert_eq!(err.offset, 10 + STREAM_ID_STRING.len());
+    assert_eq!(err.actual, "'#'");
+
+    let err = parse_error(format!("ceramic://{STREAM_ID_STRING}/a/../b"));
+    assert_eq!(err.stage, ParseStage::Url);
+    assert_eq!(err.offset, 10 + STREAM_ID_STRING.len() + 3);
+    assert_eq!(err.actual, "..");
+
+    // Percent-encoded dots are dot segments too.
+    for segment in ["%2E%2E", "%2e", ".%2e", "%2E."] {
+        let err = parse_error(format!("ceramic://{STREAM_ID_STRING}/a/{segment}/b"));
+        assert_eq!(err.stage, ParseStage::Url);
+        assert_eq!(err.offset, 10 + STREAM_ID_STRING.len() + 3);
+        assert_eq!(err.actual, segment);
+    }
+    assert!(CeramicUrl::from_str(&format!("ceramic://{STREAM_ID_STRING}/%2e%2e%2e")).is_ok());
+
+    let err = parse_error("ceramic://k!".to_string());
+    assert_eq!((err.stage, err.offset), (ParseStage::Url, 11));
+
+    let err = parse_error("ceramic://".to_string());
+    assert_eq!((err.stage, err.offset), (ParseStage::Url, 10));
+
+    let err = parse_error(format!("ceramic://{STREAM_ID_STRING}?a=1&commit=nope"));
+    assert_eq!(err.stage, ParseStage::CommitCid);
+    assert_eq!(err.offset, 10 + STREAM_ID_STRING.len() + 12);
+
+    let err = parse_error(format!(
+        "ceramic://{STREAM_ID_STRING}?commit={COMMIT_CID_STRING}&version=0"
+    ));
+    assert_eq!(err.stage, ParseStage::CommitCid);
+    assert_eq!(err.actual, "0");
+}
+
+#[cfg(feature = "url")]
+#[test]
+fn url() {
+    let s = format!("ceramic://{STREAM_ID_WITH_COMMIT}/content?a=1&b=%26");
+    let url = url::Url::parse(&s).unwrap();
+    assert_eq!(url.host_str(), Some(STREAM_ID_WITH_COMMIT));
+
+    let ceramic_url = CeramicUrl::try_from(&url).unwrap();
+    assert_eq!(ceramic_url.path, url.path());
+    assert_eq!(ceramic_url.param("b"), Some("%26"));
+    assert_eq!(url::Url::try_from(&ceramic_url).unwrap(), url);
+    assert_eq!(url::Url::try_from(ceramic_url).unwrap().as_str(), s);
+
+    let url = url::Url::parse(&format!("ceramic://{STREAM_ID_STRING}#top")).unwrap();
+    assert!(CeramicUrl::try_from(url).is_err());
+}
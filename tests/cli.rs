@@ -0,0 +1,214 @@
+#![cfg(feature = "cli")]
+
+use std::{
+    env, fs,
+    io::Write,
+    path::PathBuf,
+    process::{self, Command, Output, Stdio},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use libipld::{cbor::DagCborCodec, prelude::*};
+use streamid::*;
+
+const STREAM_ID_STRING: &str = "kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s";
+const STREAM_ID_HEX: &str =
+    "ce0100018501122054b36d564eadf2dc3fa62b5e34f05066ae8affd81eb965249639f0a755d1b3bc";
+const COMMIT_CID_STRING: &str = "bagjqcgzaday6dzalvmy5ady2m5a5legq5zrbsnlxfc2bfxej532ds7htpova";
+const STREAM_ID_WITH_COMMIT: &str =
+  "k1dpgaqe3i64kjqcp801r3sn7ysi5i0k7nxvs7j351s7kewfzr3l7mdxnj7szwo4kr9mn2qki5nnj0cv836ythy1t1gya9s25cn1nexst3jxi5o3h6qprfyju";
+
+fn streamid(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_streamid"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+// A path no other test or concurrent test run writes to.
+fn temp_path(extension: &str) -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+    env::temp_dir().join(format!("streamid-cli-{}-{n}.{extension}", process::id()))
+}
+
+fn stdout(output: Output) -> String {
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn inspect() {
+    let output = stdout(streamid(&["inspect", STREAM_ID_WITH_COMMIT], ""));
+
+    assert!(output.contains("kind:        CommitID"));
+    assert!(output.contains("stream type: tile (0)"));
+    assert!(output.contains("codec:     dag-jose (0x85)"));
+    assert!(output.contains("multihash: sha2-256 (0x12)"));
+    assert!(output.contains(&format!("commit CID:  {COMMIT_CID_STRING}")));
+    assert!(output.contains(&format!("base ID:     {STREAM_ID_STRING}")));
+}
+
+#[test]
+fn inspect_json_stdin() {
+    let output = stdout(streamid(
+        &["inspect", "--json"],
+        &format!("{STREAM_ID_STRING}\n\n0x{STREAM_ID_HEX}\n"),
+    ));
+    let lines: Vec<serde_json::Value> = output
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0], lines[1]);
+    assert_eq!(lines[0]["kind"], "StreamID");
+    assert_eq!(lines[0]["stream_type"]["name"], "tile");
+    assert_eq!(lines[0]["genesis"]["multihash"]["size"], 32);
+    assert_eq!(lines[0]["commit"], serde_json::Value::Null);
+}
+
+#[test]
+fn convert() {
+    let output = stdout(streamid(&["convert", "--to", "hex", STREAM_ID_STRING], ""));
+    assert_eq!(output, format!("{STREAM_ID_HEX}\n"));
+
+    let output = stdout(streamid(
+        &["convert", "--to", "legacy-url", STREAM_ID_WITH_COMMIT],
+        "",
+    ));
+    assert_eq!(
+        output,
+        format!("/ceramic/{STREAM_ID_STRING}?commit={COMMIT_CID_STRING}\n")
+    );
+
+    let base32 = stdout(streamid(
+        &["convert", "--to", "base32", STREAM_ID_STRING],
+        "",
+    ));
+    let output = stdout(streamid(&["convert"], &base32));
+    assert_eq!(output, format!("{STREAM_ID_STRING}\n"));
+}
+
+#[test]
+fn invalid_input() {
+    let output = streamid(&["convert", "-"], &format!("bogus\n{STREAM_ID_STRING}\n"));
+
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!("{STREAM_ID_STRING}\n")
+    );
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("bogus: "));
+}
+
+const GENESIS: &str = r#"{"header":{"controllers":["did:3:kjz..."],"family":"IDX"}}"#;
+
+fn genesis() -> Ipld {
+    libipld::json::DagJsonCodec
+        .decode(GENESIS.as_bytes())
+        .unwrap()
+}
+
+#[test]
+fn genesis_dag_json() {
+    let stream_id = StreamId::from_genesis(StreamType::Tile, &genesis()).unwrap();
+
+    let output = stdout(streamid(&["genesis", "--type", "tile", "--json"], GENESIS));
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json["id"], stream_id.to_string());
+
+    let output = stdout(streamid(
+        &[
+            "genesis",
+            "-t",
+            "0",
+            "-f",
+            "json",
+            "--expect",
+            &stream_id.to_string(),
+        ],
+        GENESIS,
+    ));
+    assert!(output.contains(&format!("base ID:     {stream_id}")));
+}
+
+#[test]
+fn genesis_dag_cbor_and_car() {
+    let stream_id = StreamId::from_genesis(StreamType::Model, &genesis()).unwrap();
+    let block = DagCborCodec.encode(&genesis()).unwrap();
+
+    let cbor = temp_path("cbor");
+    fs::write(&cbor, &block).unwrap();
+    let output = streamid(
+        &[
+            "genesis",
+            "-t",
+            "model",
+            "--expect",
+            &stream_id.to_string(),
+            cbor.to_str().unwrap(),
+        ],
+        "",
+    );
+    fs::remove_file(&cbor).unwrap();
+    stdout(output);
+
+    let header = DagCborCodec
+        .encode(&Ipld::Map(
+            [
+                ("roots".into(), Ipld::List(vec![Ipld::Link(stream_id.cid)])),
+                ("version".into(), Ipld::Integer(1)),
+            ]
+            .into_iter()
+            .collect(),
+        ))
+        .unwrap();
+    let cid = stream_id.cid.to_bytes();
+    let mut car = vec![header.len() as u8];
+    car.extend(header);
+    car.push((cid.len() + block.len()) as u8);
+    car.extend(cid);
+    car.extend(block);
+
+    let path = temp_path("car");
+    fs::write(&path, &car).unwrap();
+    let output = streamid(
+        &[
+            "genesis",
+            "-t",
+            "model",
+            "--expect",
+            &stream_id.to_string(),
+            path.to_str().unwrap(),
+        ],
+        "",
+    );
+    fs::remove_file(&path).unwrap();
+    stdout(output);
+}
+
+#[test]
+fn genesis_mismatch() {
+    let output = streamid(
+        &["genesis", "--type", "model", "--expect", STREAM_ID_STRING],
+        GENESIS,
+    );
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("stream type is model, expected tile"));
+    assert!(stderr.contains("genesis CID is"));
+}
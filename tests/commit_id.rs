@@ -1,4 +1,4 @@
-use std::str::FromStr;
+use std::{collections::HashSet, str::FromStr};
 
 use cid::{multibase::decode, Cid};
 use once_cell::sync::Lazy;
@@ -178,6 +178,7 @@ fn from_string_with_commit() {
     );
 }
 
+#[cfg(feature = "regex")]
 #[test]
 fn from_string_legacy() {
     let commit_id = CommitId::from_str(STREAM_ID_WITH_0_COMMIT_LEGACY).unwrap();
@@ -195,6 +196,7 @@ fn from_string_legacy() {
     );
 }
 
+#[cfg(feature = "regex")]
 #[test]
 fn from_string_with_commit_legacy() {
     let commit_id = CommitId::from_str(STREAM_ID_WITH_COMMIT_LEGACY).unwrap();
@@ -291,6 +293,41 @@ fn to_url() {
     );
 }
 
+#[cfg(feature = "regex")]
+#[test]
+fn to_legacy_url() {
+    let genesis = CommitId {
+        stream_type: StreamType::Tile,
+        cid: *BASE_CID,
+        commit: None,
+    };
+    let commit_id = genesis.at_commit(*COMMIT_CID);
+
+    assert_eq!(genesis.to_legacy_url(), STREAM_ID_WITH_0_COMMIT_LEGACY);
+    assert_eq!(commit_id.to_legacy_url(), STREAM_ID_WITH_COMMIT_LEGACY);
+    for commit_id in [genesis, commit_id] {
+        assert_eq!(
+            CommitId::from_str(&commit_id.to_legacy_url()).unwrap(),
+            commit_id
+        );
+    }
+
+    // An explicit genesis commit keeps its CID.
+    let explicit = CommitId {
+        stream_type: StreamType::Tile,
+        cid: *BASE_CID,
+        commit: Some(*BASE_CID),
+    };
+    assert_eq!(
+        explicit.to_legacy_url(),
+        format!("{STREAM_ID_URL_LEGACY}?commit={BASE_CID_STRING}")
+    );
+    assert_eq!(
+        CommitId::from_str(&explicit.to_legacy_url()).unwrap(),
+        explicit
+    );
+}
+
 #[test]
 fn eq() {
     let commit_id = CommitId {
@@ -327,3 +364,207 @@ fn to_base_id() {
     assert_eq!(stream_id.stream_type(), commit_id.stream_type());
     assert_eq!(stream_id.cid(), commit_id.cid());
 }
+
+#[test]
+fn unknown_stream_type() {
+    let commit_id = CommitId {
+        stream_type: StreamType::from_code(300),
+        cid: *BASE_CID,
+        commit: Some(*COMMIT_CID),
+    };
+    let options = ParseOptions {
+        allow_unknown_stream_types: true,
+        ..Default::default()
+    };
+
+    assert!(CommitId::from_slice(commit_id.to_bytes()).is_err());
+    assert!(CommitId::from_str(&commit_id.to_string()).is_err());
+    assert_eq!(
+        CommitId::from_slice_with(commit_id.to_bytes(), &options).unwrap(),
+        commit_id
+    );
+    assert_eq!(
+        CommitId::from_str_with(&commit_id.to_string(), &options).unwrap(),
+        commit_id
+    );
+    assert!(commit_id
+        .to_bytes()
+        .starts_with(&commit_id.to_base_id().to_bytes()));
+}
+
+#[test]
+fn write_bytes() {
+    let commit_id = CommitId {
+        stream_type: StreamType::Tile,
+        cid: *BASE_CID,
+        commit: Some(*COMMIT_CID),
+    };
+    let mut bytes = vec![];
+
+    assert_eq!(
+        commit_id.write_bytes(&mut bytes).unwrap(),
+        commit_id.encoded_len()
+    );
+    assert_eq!(bytes, *STREAM_ID_WITH_COMMIT_BYTES);
+}
+
+#[test]
+fn read_bytes() {
+    let bytes = [
+        STREAM_ID_WITH_COMMIT_BYTES.as_slice(),
+        STREAM_ID_WITH_0_COMMIT_BYTES.as_slice(),
+        &[1, 2, 3],
+    ]
+    .concat();
+    let mut reader = bytes.as_slice();
+
+    let commit_id = CommitId::read_bytes(&mut reader).unwrap();
+    assert_eq!(commit_id.to_string(), STREAM_ID_WITH_COMMIT);
+
+    let commit_id = CommitId::read_bytes(&mut reader).unwrap();
+    assert_eq!(commit_id.to_string(), STREAM_ID_WITH_0_COMMIT);
+
+    assert_eq!(reader, &[1, 2, 3]);
+    assert!(CommitId::read_bytes(STREAM_ID_BYTES.as_slice()).is_err());
+
+    // A CIDv0 commit starts without a version byte.
+    let commit_id = CommitId {
+        stream_type: StreamType::Tile,
+        cid: *BASE_CID,
+        commit: Some(Cid::from_str("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG").unwrap()),
+    };
+    let bytes = [commit_id.to_bytes().as_slice(), &[0]].concat();
+    let mut reader = bytes.as_slice();
+    assert_eq!(CommitId::read_bytes(&mut reader).unwrap(), commit_id);
+    assert_eq!(reader, &[0]);
+}
+
+#[test]
+fn read_bytes_strict() {
+    let options = ParseOptions {
+        strict: true,
+        ..Default::default()
+    };
+    let redundant = CommitId {
+        stream_type: StreamType::Tile,
+        cid: *BASE_CID,
+        commit: Some(*BASE_CID),
+    }
+    .to_bytes();
+
+    assert!(CommitId::read_bytes_with(STREAM_ID_WITH_COMMIT_BYTES.as_slice(), &options).is_ok());
+    assert!(CommitId::read_bytes(redundant.as_slice()).is_ok());
+    match CommitId::read_bytes_with(redundant.as_slice(), &options).unwrap_err() {
+        Error::Parse(err) => {
+            assert_eq!(err.stage, ParseStage::CommitCid);
+            assert_eq!(err.offset, STREAM_ID_BYTES.len());
+        }
+        err => panic!("unexpected error {err}"),
+    }
+}
+
+#[test]
+fn from_bytes_strict() {
+    let options = ParseOptions {
+        strict: true,
+        ..Default::default()
+    };
+    let redundant = CommitId {
+        stream_type: StreamType::Tile,
+        cid: *BASE_CID,
+        commit: Some(*BASE_CID),
+    };
+    let trailing = [STREAM_ID_WITH_COMMIT_BYTES.as_slice(), &[1, 2, 3]].concat();
+
+    assert!(CommitId::from_slice_with(&*STREAM_ID_WITH_COMMIT_BYTES, &options).is_ok());
+    assert!(CommitId::from_slice_with(&*STREAM_ID_WITH_0_COMMIT_BYTES, &options).is_ok());
+    assert!(CommitId::from_slice(redundant.to_bytes()).is_ok());
+    assert!(CommitId::from_slice_with(redundant.to_bytes(), &options).is_err());
+    assert!(CommitId::from_slice(&trailing).is_ok());
+    assert!(CommitId::from_slice_with(&trailing, &options).is_err());
+}
+
+#[test]
+fn from_string_strict() {
+    let options = ParseOptions {
+        strict: true,
+        ..Default::default()
+    };
+
+    assert!(CommitId::from_str_with(STREAM_ID_WITH_COMMIT, &options).is_ok());
+    assert!(CommitId::from_str_with(STREAM_ID_WITH_COMMIT_LEGACY, &options).is_err());
+    assert!(CommitId::from_str_with(STREAM_ID_WITH_0_COMMIT_LEGACY, &options).is_err());
+}
+
+#[test]
+fn is_genesis() {
+    let zero = CommitId {
+        stream_type: StreamType::Tile,
+        cid: *BASE_CID,
+        commit: None,
+    };
+    let explicit = CommitId {
+        commit: Some(*BASE_CID),
+        ..zero.clone()
+    };
+    let commit_id = CommitId {
+        commit: Some(*COMMIT_CID),
+        ..zero.clone()
+    };
+
+    assert!(zero.is_genesis());
+    assert!(explicit.is_genesis());
+    assert!(!commit_id.is_genesis());
+}
+
+#[test]
+fn normalize() {
+    let zero = CommitId {
+        stream_type: StreamType::Tile,
+        cid: *BASE_CID,
+        commit: None,
+    };
+    let mut explicit = CommitId {
+        commit: Some(*BASE_CID),
+        ..zero.clone()
+    };
+    let commit_id = CommitId {
+        commit: Some(*COMMIT_CID),
+        ..zero.clone()
+    };
+
+    assert_ne!(explicit, zero);
+    assert_eq!(explicit.canonical(), zero);
+    assert_eq!(commit_id.canonical(), commit_id);
+
+    explicit.normalize();
+
+    assert_eq!(explicit, zero);
+    assert_eq!(explicit.to_bytes(), *STREAM_ID_WITH_0_COMMIT_BYTES);
+}
+
+#[test]
+fn canonical_commit_id() {
+    let zero = CommitId {
+        stream_type: StreamType::Tile,
+        cid: *BASE_CID,
+        commit: None,
+    };
+    let explicit = CommitId {
+        commit: Some(*BASE_CID),
+        ..zero.clone()
+    };
+    let commit_id = CommitId {
+        commit: Some(*COMMIT_CID),
+        ..zero.clone()
+    };
+
+    let keys: HashSet<CanonicalCommitId> = [zero.clone(), explicit, commit_id]
+        .into_iter()
+        .map(CanonicalCommitId::from)
+        .collect();
+
+    assert_eq!(keys.len(), 2);
+    assert!(keys.contains(&CanonicalCommitId::from(zero.clone())));
+    assert_eq!(CanonicalCommitId::from(zero.clone()).into_inner(), zero);
+}
@@ -0,0 +1,118 @@
+use std::str::FromStr;
+
+use cid::{multibase::Base, Cid};
+use once_cell::sync::Lazy;
+use streamid::*;
+
+const BASE_CID_STRING: &str = "bagcqcerakszw2vsovxznyp5gfnpdj4cqm2xiv76yd24wkjewhhykovorwo6a";
+static BASE_CID: Lazy<Cid> = Lazy::new(|| Cid::from_str(BASE_CID_STRING).unwrap());
+const COMMIT_CID_STRING: &str = "bagjqcgzaday6dzalvmy5ady2m5a5legq5zrbsnlxfc2bfxej532ds7htpova";
+const STREAM_ID_STRING: &str = "kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s";
+#[cfg(feature = "regex")]
+const STREAM_ID_URL: &str =
+    "ceramic://kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s";
+const STREAM_ID_WITH_COMMIT: &str =
+  "k1dpgaqe3i64kjqcp801r3sn7ysi5i0k7nxvs7j351s7kewfzr3l7mdxnj7szwo4kr9mn2qki5nnj0cv836ythy1t1gya9s25cn1nexst3jxi5o3h6qprfyju";
+
+#[test]
+fn stream_id() {
+    let diagnosis = diagnose(STREAM_ID_STRING);
+
+    assert_eq!(
+        diagnosis.kind,
+        InputKind::StreamId(StreamId::from_str(STREAM_ID_STRING).unwrap())
+    );
+    assert_eq!(diagnosis.base, Some(Base::Base36Lower));
+    assert_eq!(diagnosis.suggestion, None);
+    assert_eq!(diagnosis.error, None);
+    assert_eq!(diagnosis.to_string(), "StreamID of tile stream");
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn stream_id_url() {
+    let diagnosis = diagnose(STREAM_ID_URL);
+
+    assert!(matches!(diagnosis.kind, InputKind::StreamId(_)));
+    assert_eq!(diagnosis.suggestion.as_deref(), Some(STREAM_ID_STRING));
+    assert_eq!(
+        diagnosis.to_string(),
+        format!("StreamID of tile stream; canonical form is {STREAM_ID_STRING}")
+    );
+}
+
+#[test]
+fn other_base() {
+    let stream_id = StreamId::from_str(STREAM_ID_STRING).unwrap();
+    let diagnosis = diagnose(&stream_id.to_string_of_base(Base::Base58Btc));
+
+    assert_eq!(diagnosis.base, Some(Base::Base58Btc));
+    assert_eq!(diagnosis.suggestion.as_deref(), Some(STREAM_ID_STRING));
+    assert_eq!(
+        diagnosis.to_string(),
+        format!(
+            "StreamID of tile stream, base58btc multibase rather than base36; canonical form is {STREAM_ID_STRING}"
+        )
+    );
+}
+
+#[test]
+fn commit_id() {
+    let diagnosis = diagnose(STREAM_ID_WITH_COMMIT);
+
+    assert!(matches!(diagnosis.kind, InputKind::CommitId(_)));
+    assert_eq!(diagnosis.suggestion.as_deref(), Some(STREAM_ID_STRING));
+    assert_eq!(
+        diagnosis.to_string(),
+        format!(
+            "CommitID of tile stream at commit {COMMIT_CID_STRING}, not a StreamID; its StreamID is {STREAM_ID_STRING}"
+        )
+    );
+}
+
+#[test]
+fn unknown_stream_type() {
+    let stream_id = StreamId {
+        stream_type: StreamType::from_code(9),
+        cid: *BASE_CID,
+    };
+    let diagnosis = diagnose(&stream_id.to_string());
+
+    assert_eq!(diagnosis.kind, InputKind::StreamId(stream_id.clone()));
+    assert_eq!(
+        diagnosis.error.as_ref().unwrap().stage,
+        ParseStage::StreamType
+    );
+    assert_eq!(
+        diagnosis.to_string(),
+        "StreamID of unknown stream type 9 stream"
+    );
+}
+
+#[test]
+fn cid() {
+    let diagnosis = diagnose(BASE_CID_STRING);
+
+    assert_eq!(diagnosis.kind, InputKind::Cid(*BASE_CID));
+    assert_eq!(diagnosis.error.as_ref().unwrap().stage, ParseStage::Codec);
+}
+
+#[test]
+fn did_key() {
+    let diagnosis = diagnose("did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK");
+
+    assert_eq!(diagnosis.kind, InputKind::DidKey);
+    assert_eq!(diagnosis.error.as_ref().unwrap().stage, ParseStage::Url);
+    assert_eq!(diagnosis.to_string(), "did:key DID, not a StreamID");
+}
+
+#[test]
+fn invalid() {
+    let diagnosis = diagnose("kjzl6cwe1");
+
+    assert_eq!(diagnosis.kind, InputKind::Invalid);
+    assert_eq!(diagnosis.base, Some(Base::Base36Lower));
+    assert!(diagnosis
+        .to_string()
+        .starts_with("not a StreamID: Invalid StreamRef kjzl6cwe1"));
+}
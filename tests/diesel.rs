@@ -0,0 +1,81 @@
+#![cfg(feature = "diesel-sqlite")]
+
+use std::str::FromStr;
+
+use diesel::{prelude::*, result::Error as DieselError, sql_query, sqlite::SqliteConnection};
+use streamid::*;
+
+const STREAM_ID_STRING: &str = "kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s";
+const STREAM_ID_WITH_COMMIT: &str =
+  "k1dpgaqe3i64kjqcp801r3sn7ysi5i0k7nxvs7j351s7kewfzr3l7mdxnj7szwo4kr9mn2qki5nnj0cv836ythy1t1gya9s25cn1nexst3jxi5o3h6qprfyju";
+
+diesel::table! {
+    ids (rowid) {
+        rowid -> Integer,
+        stream_id -> Nullable<Text>,
+        commit_id -> Nullable<Binary>,
+    }
+}
+
+fn connect() -> SqliteConnection {
+    let mut conn = SqliteConnection::establish(":memory:").unwrap();
+    sql_query("CREATE TABLE ids (stream_id TEXT, commit_id BLOB)")
+        .execute(&mut conn)
+        .unwrap();
+    conn
+}
+
+#[test]
+fn text_and_binary() {
+    let mut conn = connect();
+    let stream_id = StreamId::from_str(STREAM_ID_STRING).unwrap();
+    let commit_id = CommitId::from_str(STREAM_ID_WITH_COMMIT).unwrap();
+    diesel::insert_into(ids::table)
+        .values((
+            ids::stream_id.eq(&stream_id),
+            ids::commit_id.eq(StreamRef::CommitId(commit_id.clone())),
+        ))
+        .execute(&mut conn)
+        .unwrap();
+
+    let (text, bytes): (Option<String>, Option<Vec<u8>>) = ids::table
+        .select((ids::stream_id, ids::commit_id))
+        .first(&mut conn)
+        .unwrap();
+    assert_eq!(text.unwrap(), STREAM_ID_STRING);
+    assert_eq!(bytes.unwrap(), commit_id.to_bytes());
+
+    let (read_stream_id, read_commit_id): (Option<StreamId>, Option<CommitId>) = ids::table
+        .select((ids::stream_id, ids::commit_id))
+        .filter(ids::stream_id.eq(&stream_id))
+        .first(&mut conn)
+        .unwrap();
+    assert_eq!(read_stream_id, Some(stream_id));
+    assert_eq!(read_commit_id, Some(commit_id));
+}
+
+#[test]
+fn invalid() {
+    let mut conn = connect();
+    sql_query("INSERT INTO ids (stream_id, commit_id) VALUES ('invalid', x'ce01')")
+        .execute(&mut conn)
+        .unwrap();
+
+    let err = ids::table
+        .select(ids::stream_id)
+        .first::<Option<StreamId>>(&mut conn)
+        .unwrap_err();
+    match err {
+        DieselError::DeserializationError(err) => assert!(err.is::<Error>()),
+        err => panic!("unexpected error {err}"),
+    }
+
+    let err = ids::table
+        .select(ids::commit_id)
+        .first::<Option<CommitId>>(&mut conn)
+        .unwrap_err();
+    match err {
+        DieselError::DeserializationError(err) => assert!(err.is::<Error>()),
+        err => panic!("unexpected error {err}"),
+    }
+}
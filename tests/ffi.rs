@@ -0,0 +1,136 @@
+#![cfg(feature = "ffi")]
+
+use std::{
+    ffi::{CStr, CString},
+    ptr,
+    str::FromStr,
+};
+
+use streamid::{ffi::*, StreamRef};
+
+const BASE_CID_STRING: &str = "bagcqcerakszw2vsovxznyp5gfnpdj4cqm2xiv76yd24wkjewhhykovorwo6a";
+const COMMIT_CID_STRING: &str = "bagjqcgzaday6dzalvmy5ady2m5a5legq5zrbsnlxfc2bfxej532ds7htpova";
+const STREAM_ID_STRING: &str = "kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s";
+const STREAM_ID_WITH_COMMIT: &str =
+  "k1dpgaqe3i64kjqcp801r3sn7ysi5i0k7nxvs7j351s7kewfzr3l7mdxnj7szwo4kr9mn2qki5nnj0cv836ythy1t1gya9s25cn1nexst3jxi5o3h6qprfyju";
+const STREAM_ID_WITH_0_COMMIT: &str =
+    "k3y52l7qbv1frxwipl4hp7e6jlu4f6u8upm2xv0irmedfkm5cnutmezzi3u7mytj4";
+
+unsafe fn take_string(s: *mut std::ffi::c_char) -> String {
+    let string = CStr::from_ptr(s).to_str().unwrap().to_owned();
+    streamid_string_free(s);
+    string
+}
+
+#[test]
+fn parse_and_encode() {
+    let input = CString::new(STREAM_ID_STRING).unwrap();
+    let mut stream_id: *mut StreamRef = ptr::null_mut();
+
+    unsafe {
+        assert_eq!(
+            streamid_from_str(input.as_ptr(), &mut stream_id),
+            StreamIdError::Ok
+        );
+        assert_eq!(streamid_stream_type(stream_id), 0);
+        assert!(!streamid_is_commit_id(stream_id));
+        assert_eq!(take_string(streamid_cid(stream_id)), BASE_CID_STRING);
+        assert!(streamid_commit(stream_id).is_null());
+        assert_eq!(take_string(streamid_to_base36(stream_id)), STREAM_ID_STRING);
+        assert_eq!(
+            take_string(streamid_to_url(stream_id)),
+            format!("ceramic://{STREAM_ID_STRING}")
+        );
+
+        let mut buf = vec![0; streamid_encoded_len(stream_id)];
+        let mut len = 0;
+        assert_eq!(
+            streamid_to_bytes(stream_id, buf.as_mut_ptr(), buf.len(), &mut len),
+            StreamIdError::Ok
+        );
+        assert_eq!(len, buf.len());
+        assert_eq!(
+            streamid_to_bytes(stream_id, buf.as_mut_ptr(), 1, &mut len),
+            StreamIdError::BufferTooSmall
+        );
+
+        let mut parsed: *mut StreamRef = ptr::null_mut();
+        assert_eq!(
+            streamid_from_bytes(buf.as_ptr(), buf.len(), &mut parsed),
+            StreamIdError::Ok
+        );
+        assert_eq!(*parsed, *stream_id);
+
+        streamid_free(parsed);
+        streamid_free(stream_id);
+    }
+}
+
+#[test]
+fn at_commit() {
+    let input = CString::new(STREAM_ID_STRING).unwrap();
+    let commit = CString::new(COMMIT_CID_STRING).unwrap();
+    let mut stream_id: *mut StreamRef = ptr::null_mut();
+    let mut commit_id: *mut StreamRef = ptr::null_mut();
+
+    unsafe {
+        streamid_from_str(input.as_ptr(), &mut stream_id);
+        assert_eq!(
+            streamid_at_commit(stream_id, commit.as_ptr(), &mut commit_id),
+            StreamIdError::Ok
+        );
+        assert!(streamid_is_commit_id(commit_id));
+        assert_eq!(take_string(streamid_commit(commit_id)), COMMIT_CID_STRING);
+        assert_eq!(
+            take_string(streamid_to_base36(commit_id)),
+            STREAM_ID_WITH_COMMIT
+        );
+
+        streamid_free(commit_id);
+
+        // Zero commit
+        let zero = CString::new("0").unwrap();
+        assert_eq!(
+            streamid_at_commit(stream_id, zero.as_ptr(), &mut commit_id),
+            StreamIdError::Ok
+        );
+        assert_eq!(take_string(streamid_commit(commit_id)), BASE_CID_STRING);
+        assert_eq!(
+            *commit_id,
+            StreamRef::from_str(STREAM_ID_WITH_0_COMMIT).unwrap()
+        );
+
+        streamid_free(commit_id);
+        streamid_free(stream_id);
+    }
+}
+
+#[test]
+fn errors() {
+    let mut stream_ref: *mut StreamRef = ptr::null_mut();
+    let cid = CString::new(BASE_CID_STRING).unwrap();
+
+    unsafe {
+        assert_eq!(
+            streamid_from_str(cid.as_ptr(), &mut stream_ref),
+            StreamIdError::ParseCodec
+        );
+        assert_eq!(
+            streamid_from_str(ptr::null(), &mut stream_ref),
+            StreamIdError::NullPointer
+        );
+        assert_eq!(
+            streamid_from_bytes([0xce, 0x01, 0x09].as_ptr(), 3, &mut stream_ref),
+            StreamIdError::ParseStreamType
+        );
+        assert!(stream_ref.is_null());
+
+        assert_eq!(streamid_stream_type(ptr::null()), u64::MAX);
+        assert!(!streamid_is_commit_id(ptr::null()));
+        assert!(streamid_cid(ptr::null()).is_null());
+        assert!(streamid_commit(ptr::null()).is_null());
+        assert!(streamid_to_base36(ptr::null()).is_null());
+        assert!(streamid_to_url(ptr::null()).is_null());
+        assert_eq!(streamid_encoded_len(ptr::null()), 0);
+    }
+}
@@ -0,0 +1,72 @@
+// Writes fixtures.json, the genesis commits of tests/genesis.rs as js-ceramic builds and encodes
+// them, with the versions of the packages that produced them.
+//
+// Regenerating needs network access and is a separate step from `cargo test`. Pin the packages to
+// exact versions once, then install from the lockfile and generate:
+//
+//   npm run pin    # rewrites package.json with exact versions and writes package-lock.json
+//   npm ci && npm run generate
+//
+// Commit package.json, package-lock.json and fixtures.json together. Until fixtures.json is
+// committed the fixture tests in tests/genesis.rs are ignored.
+import { readFileSync, writeFileSync } from 'node:fs'
+
+import { ModelInstanceDocument } from '@ceramicnetwork/stream-model-instance'
+import { TileDocument } from '@ceramicnetwork/stream-tile'
+import { StreamID } from '@ceramicnetwork/streamid'
+import * as dagCbor from '@ipld/dag-cbor'
+
+const DID = 'did:key:z6MkgSV3tAuw7gUWqKCUY7ae6uWNxqYgdwPhUJbJhF9EFXm9'
+const MODEL = 'kjzl6hvfrbw6c5ajfmes842lu09vjxu5956e3xq0xk12gp2jcf9s90cagt2god9'
+const SCHEMA =
+  'k1dpgaqe3i64kjqcp801r3sn7ysi5i0k7nxvs7j351s7kewfzr3l7mdxnj7szwo4kr9mn2qki5nnj0cv836ythy1t1gya9s25cn1nexst3jxi5o3h6qprfyju'
+
+const PACKAGES = [
+  '@ceramicnetwork/stream-model-instance',
+  '@ceramicnetwork/stream-tile',
+  '@ceramicnetwork/streamid',
+  '@ipld/dag-cbor',
+]
+
+function version(name) {
+  const path = new URL(`node_modules/${name}/package.json`, import.meta.url)
+  return JSON.parse(readFileSync(path, 'utf8')).version
+}
+
+function fixture(type, genesis) {
+  return {
+    genesis: Buffer.from(dagCbor.encode(genesis)).toString('hex'),
+    streamId: StreamID.fromGenesis(type, genesis).toString(),
+  }
+}
+
+// TileDocument writes random bytes as a base64 `unique` string, set a fixed one instead.
+const tile = await TileDocument.makeGenesis(undefined, null, {
+  controllers: [DID],
+  family: 'IDX',
+  tags: ['profile', 'public'],
+  schema: SCHEMA,
+  deterministic: true,
+})
+tile.header.unique = Buffer.from([...Array(12).keys()]).toString('base64')
+
+// `single` and `set` hand their deterministic genesis commit to the node, keep it instead.
+const ceramic = {
+  async createStreamFromGenesis(type, genesis) {
+    return genesis
+  },
+}
+const metadata = { model: StreamID.fromString(MODEL), controller: DID }
+const single = await ModelInstanceDocument.single(ceramic, metadata)
+const set = await ModelInstanceDocument.set(ceramic, metadata, ['alice', 'work'])
+const emptySet = await ModelInstanceDocument.set(ceramic, metadata, [])
+
+const fixtures = {
+  versions: Object.fromEntries(PACKAGES.map((name) => [name, version(name)])),
+  tile: fixture('tile', tile),
+  single: fixture('MID', single),
+  set: fixture('MID', set),
+  emptySet: fixture('MID', emptySet),
+}
+
+writeFileSync(new URL('fixtures.json', import.meta.url), JSON.stringify(fixtures, null, 2) + '\n')
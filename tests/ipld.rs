@@ -0,0 +1,105 @@
+#![cfg(feature = "ipld")]
+
+use std::str::FromStr;
+
+use libipld::{cbor::DagCborCodec, json::DagJsonCodec, prelude::*};
+use streamid::*;
+
+const STREAM_ID_STRING: &str = "kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s";
+const STREAM_ID_WITH_COMMIT: &str =
+  "k1dpgaqe3i64kjqcp801r3sn7ysi5i0k7nxvs7j351s7kewfzr3l7mdxnj7szwo4kr9mn2qki5nnj0cv836ythy1t1gya9s25cn1nexst3jxi5o3h6qprfyju";
+
+#[test]
+fn to_ipld() {
+    let stream_id = StreamId::from_str(STREAM_ID_STRING).unwrap();
+    let commit_id = CommitId::from_str(STREAM_ID_WITH_COMMIT).unwrap();
+
+    assert_eq!(Ipld::from(&stream_id), Ipld::Bytes(stream_id.to_bytes()));
+    assert_eq!(Ipld::from(&commit_id), Ipld::Bytes(commit_id.to_bytes()));
+    assert_eq!(
+        Ipld::from(StreamRef::CommitId(commit_id.clone())),
+        Ipld::Bytes(commit_id.to_bytes())
+    );
+}
+
+#[test]
+fn from_ipld() {
+    let stream_id = StreamId::from_str(STREAM_ID_STRING).unwrap();
+    let commit_id = CommitId::from_str(STREAM_ID_WITH_COMMIT).unwrap();
+
+    assert_eq!(
+        StreamId::try_from(Ipld::from(&stream_id)).unwrap(),
+        stream_id
+    );
+    assert_eq!(
+        StreamId::try_from(Ipld::String(STREAM_ID_STRING.into())).unwrap(),
+        stream_id
+    );
+    assert_eq!(
+        CommitId::try_from(Ipld::from(&commit_id)).unwrap(),
+        commit_id
+    );
+    assert_eq!(
+        StreamRef::try_from(Ipld::String(STREAM_ID_WITH_COMMIT.into())).unwrap(),
+        StreamRef::CommitId(commit_id)
+    );
+}
+
+#[test]
+fn from_ipld_err() {
+    assert!(StreamId::try_from(Ipld::String(STREAM_ID_WITH_COMMIT.into())).is_err());
+    assert!(CommitId::try_from(Ipld::String(STREAM_ID_STRING.into())).is_err());
+    assert!(StreamRef::try_from(Ipld::Integer(1)).is_err());
+    assert!(StreamRef::try_from(Ipld::Bytes(vec![1, 2, 3])).is_err());
+}
+
+#[test]
+fn dag_cbor_roundtrip() {
+    let stream_id = StreamId::from_str(STREAM_ID_STRING).unwrap();
+    let commit_id = CommitId::from_str(STREAM_ID_WITH_COMMIT).unwrap();
+
+    let bytes = DagCborCodec.encode(&stream_id).unwrap();
+    assert_eq!(DagCborCodec.decode::<StreamId>(&bytes).unwrap(), stream_id);
+
+    let bytes = DagCborCodec.encode(&commit_id).unwrap();
+    assert_eq!(DagCborCodec.decode::<CommitId>(&bytes).unwrap(), commit_id);
+    assert_eq!(
+        DagCborCodec.decode::<StreamRef>(&bytes).unwrap(),
+        StreamRef::CommitId(commit_id)
+    );
+}
+
+#[test]
+fn dag_json_roundtrip() {
+    let stream_id = StreamId::from_str(STREAM_ID_STRING).unwrap();
+
+    let bytes = DagJsonCodec.encode(&stream_id).unwrap();
+    assert_eq!(DagJsonCodec.decode::<StreamId>(&bytes).unwrap(), stream_id);
+
+    let bytes = DagJsonCodec
+        .encode(&Ipld::String(STREAM_ID_STRING.into()))
+        .unwrap();
+    assert_eq!(DagJsonCodec.decode::<StreamId>(&bytes).unwrap(), stream_id);
+}
+
+#[test]
+fn embedded_in_document() {
+    let stream_id = StreamId::from_str(STREAM_ID_STRING).unwrap();
+    let document = Ipld::Map(
+        [
+            ("title".into(), "Hello".into()),
+            ("linkedDoc".into(), stream_id.clone().into()),
+        ]
+        .into_iter()
+        .collect(),
+    );
+
+    let bytes = DagCborCodec.encode(&document).unwrap();
+    let decoded: Ipld = DagCborCodec.decode(&bytes).unwrap();
+    let linked = match decoded {
+        Ipld::Map(mut map) => map.remove("linkedDoc").unwrap(),
+        _ => panic!("expected a map"),
+    };
+
+    assert_eq!(StreamId::try_from(linked).unwrap(), stream_id);
+}
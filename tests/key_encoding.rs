@@ -0,0 +1,148 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+use cid::Cid;
+use once_cell::sync::Lazy;
+use streamid::*;
+
+const BASE_CID_STRING: &str = "bagcqcerakszw2vsovxznyp5gfnpdj4cqm2xiv76yd24wkjewhhykovorwo6a";
+static BASE_CID: Lazy<Cid> = Lazy::new(|| Cid::from_str(BASE_CID_STRING).unwrap());
+const COMMIT_CID_STRING: &str = "bagjqcgzaday6dzalvmy5ady2m5a5legq5zrbsnlxfc2bfxej532ds7htpova";
+static COMMIT_CID: Lazy<Cid> = Lazy::new(|| Cid::from_str(COMMIT_CID_STRING).unwrap());
+const CIDV0_STRING: &str = "QmbWqxBEKC3P8tqsKc98xmWNzrzDtRLMiMPL8wBuTGsMnR";
+static CIDV0: Lazy<Cid> = Lazy::new(|| Cid::from_str(CIDV0_STRING).unwrap());
+
+fn new_stream_id(stream_type: StreamType, cid: Cid) -> StreamId {
+    StreamId { stream_type, cid }
+}
+
+#[test]
+fn round_trip() {
+    let stream_id = new_stream_id(StreamType::Tile, *BASE_CID);
+    let genesis = stream_id.at_commit(*BASE_CID);
+    let commit = stream_id.at_commit(*COMMIT_CID);
+    let cidv0_commit = stream_id.at_commit(*CIDV0);
+
+    assert_eq!(stream_id.to_key(), stream_id.to_bytes());
+    assert_eq!(StreamId::from_key(&stream_id.to_key()).unwrap(), stream_id);
+    assert_eq!(CommitId::from_key(&commit.to_key()).unwrap(), commit);
+    assert_eq!(
+        CommitId::from_key(&cidv0_commit.to_key()).unwrap(),
+        cidv0_commit
+    );
+    for stream_ref in [
+        StreamRef::StreamId(stream_id.clone()),
+        StreamRef::CommitId(commit.clone()),
+    ] {
+        assert_eq!(
+            StreamRef::from_key(&stream_ref.to_key()).unwrap(),
+            stream_ref
+        );
+    }
+
+    let unknown = new_stream_id(StreamType::from_code(300), *BASE_CID);
+    assert_eq!(StreamId::from_key(&unknown.to_key()).unwrap(), unknown);
+
+    // An explicit genesis commit is the genesis key.
+    let zero = CommitId {
+        commit: None,
+        ..genesis.clone()
+    };
+    assert_eq!(genesis.to_key(), zero.to_key());
+    assert_eq!(CommitId::from_key(&genesis.to_key()).unwrap(), zero);
+    assert_eq!(genesis.to_key().last(), Some(&0x00));
+}
+
+#[test]
+fn stream_range() {
+    let tile = new_stream_id(StreamType::Tile, *BASE_CID);
+    let other_cid = new_stream_id(StreamType::Tile, *COMMIT_CID);
+    let other_type = new_stream_id(StreamType::Caip10Link, *BASE_CID);
+
+    let mut keys = BTreeMap::new();
+    for (value, stream_ref) in [
+        StreamRef::CommitId(other_cid.at_commit(*BASE_CID)),
+        StreamRef::CommitId(tile.at_commit(*CIDV0)),
+        StreamRef::StreamId(other_type.clone()),
+        StreamRef::CommitId(tile.at_commit(*COMMIT_CID)),
+        StreamRef::StreamId(other_cid.clone()),
+        StreamRef::CommitId(tile.at_commit(*BASE_CID)),
+        StreamRef::CommitId(other_type.at_commit(*COMMIT_CID)),
+        StreamRef::StreamId(tile.clone()),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        keys.insert(stream_ref.to_key(), value);
+    }
+
+    let values: Vec<_> = keys
+        .range(tile.stream_key_prefix()..tile.stream_key_upper_bound())
+        .map(|(_, value)| *value)
+        .collect();
+    // StreamId, genesis, then commits by CID bytes: CIDv1 before CIDv0.
+    assert_eq!(values, [7, 5, 3, 1]);
+
+    for (key, _) in keys.range(tile.stream_key_prefix()..tile.stream_key_upper_bound()) {
+        assert!(key.starts_with(&tile.stream_key_prefix()));
+        assert_eq!(StreamRef::from_key(key).unwrap().to_base_id(), tile);
+    }
+
+    let commit = tile.at_commit(*COMMIT_CID);
+    assert_eq!(commit.stream_key_prefix(), tile.stream_key_prefix());
+    assert_eq!(
+        commit.stream_key_upper_bound(),
+        tile.stream_key_upper_bound()
+    );
+}
+
+#[test]
+fn invalid() {
+    let stream_id = new_stream_id(StreamType::Tile, *BASE_CID);
+    let commit = stream_id.at_commit(*COMMIT_CID);
+    let prefix_len = stream_id.to_key().len();
+
+    let Error::Parse(err) = StreamId::from_key(&commit.to_key()).unwrap_err() else {
+        panic!()
+    };
+    assert_eq!(err.stage, ParseStage::Kind);
+    assert_eq!(err.offset, prefix_len);
+    assert_eq!(err.expected, "StreamID");
+
+    let Error::Parse(err) = CommitId::from_key(&stream_id.to_key()).unwrap_err() else {
+        panic!()
+    };
+    assert_eq!(err.stage, ParseStage::Kind);
+    assert_eq!(err.offset, prefix_len);
+    assert_eq!(err.actual, "StreamID");
+
+    let mut key = stream_id.to_key();
+    key.push(0x02);
+    let Error::Parse(err) = StreamRef::from_key(&key).unwrap_err() else {
+        panic!()
+    };
+    assert_eq!(err.stage, ParseStage::Kind);
+    assert_eq!(err.actual, "0x02");
+
+    let mut key = commit.to_key();
+    key.push(0x00);
+    let Error::Parse(err) = StreamRef::from_key(&key).unwrap_err() else {
+        panic!()
+    };
+    assert_eq!(err.stage, ParseStage::TrailingData);
+    assert_eq!(err.offset, key.len() - 1);
+
+    // The genesis commit only has the `0x00` key.
+    let mut key = stream_id.to_key();
+    key.push(0x01);
+    key.extend(BASE_CID.to_bytes());
+    let Error::Parse(err) = CommitId::from_key(&key).unwrap_err() else {
+        panic!()
+    };
+    assert_eq!(err.stage, ParseStage::CommitCid);
+    assert_eq!(err.offset, prefix_len + 1);
+    assert_eq!(err.actual, "genesis CID");
+    assert!(StreamRef::from_key(&key).is_err());
+
+    // Plain `to_bytes` of a commit is not a key.
+    assert!(CommitId::from_key(&commit.to_bytes()).is_err());
+}
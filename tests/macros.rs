@@ -0,0 +1,38 @@
+#![cfg(feature = "macros")]
+
+use std::str::FromStr;
+
+use streamid::*;
+
+const STREAM_ID_STRING: &str = "kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s";
+const STREAM_ID_WITH_COMMIT: &str =
+  "k1dpgaqe3i64kjqcp801r3sn7ysi5i0k7nxvs7j351s7kewfzr3l7mdxnj7szwo4kr9mn2qki5nnj0cv836ythy1t1gya9s25cn1nexst3jxi5o3h6qprfyju";
+const STREAM_ID_WITH_0_COMMIT: &str =
+    "k3y52l7qbv1frxwipl4hp7e6jlu4f6u8upm2xv0irmedfkm5cnutmezzi3u7mytj4";
+
+const STREAM_ID: StreamId =
+    stream_id!("kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s");
+static COMMIT_ID: CommitId = commit_id!("k1dpgaqe3i64kjqcp801r3sn7ysi5i0k7nxvs7j351s7kewfzr3l7mdxnj7szwo4kr9mn2qki5nnj0cv836ythy1t1gya9s25cn1nexst3jxi5o3h6qprfyju");
+
+#[test]
+fn stream_id() {
+    assert_eq!(STREAM_ID, StreamId::from_str(STREAM_ID_STRING).unwrap());
+    assert_eq!(STREAM_ID.stream_type, StreamType::Tile);
+}
+
+#[test]
+fn commit_id() {
+    assert_eq!(
+        COMMIT_ID,
+        CommitId::from_str(STREAM_ID_WITH_COMMIT).unwrap()
+    );
+    assert_eq!(
+        commit_id!("k3y52l7qbv1frxwipl4hp7e6jlu4f6u8upm2xv0irmedfkm5cnutmezzi3u7mytj4"),
+        CommitId::from_str(STREAM_ID_WITH_0_COMMIT).unwrap()
+    );
+}
+
+#[test]
+fn invalid() {
+    trybuild::TestCases::new().compile_fail("tests/macros/*.rs");
+}
@@ -0,0 +1,7 @@
+use streamid::*;
+
+const BASE32: StreamId = stream_id!("bagcqcerakszw2vsovxznyp5gfnpdj4cqm2xiv76yd24wkjewhhykovorwo6a");
+const TYPO: StreamId = stream_id!("kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60");
+const NOT_BASE36: StreamId = stream_id!("kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp6_s");
+
+fn main() {}
@@ -0,0 +1,6 @@
+use streamid::*;
+
+const STREAM_ID: StreamId = stream_id!("k1dpgaqe3i64kjqcp801r3sn7ysi5i0k7nxvs7j351s7kewfzr3l7mdxnj7szwo4kr9mn2qki5nnj0cv836ythy1t1gya9s25cn1nexst3jxi5o3h6qprfyju");
+const COMMIT_ID: CommitId = commit_id!("kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s");
+
+fn main() {}
@@ -0,0 +1,179 @@
+use std::str::FromStr;
+
+use cid::{multibase::decode, Cid};
+use once_cell::sync::Lazy;
+use streamid::*;
+
+const BASE_CID_STRING: &str = "bagcqcerakszw2vsovxznyp5gfnpdj4cqm2xiv76yd24wkjewhhykovorwo6a";
+static BASE_CID: Lazy<Cid> = Lazy::new(|| Cid::from_str(BASE_CID_STRING).unwrap());
+const STREAM_ID_STRING: &str = "kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s";
+static STREAM_ID_BYTES: Lazy<Vec<u8>> = Lazy::new(|| decode(STREAM_ID_STRING).unwrap().1);
+const STREAM_ID_WITH_COMMIT: &str =
+  "k1dpgaqe3i64kjqcp801r3sn7ysi5i0k7nxvs7j351s7kewfzr3l7mdxnj7szwo4kr9mn2qki5nnj0cv836ythy1t1gya9s25cn1nexst3jxi5o3h6qprfyju";
+
+fn parse_error(err: Error) -> ParseError {
+    match err {
+        Error::Parse(err) => err,
+        err => panic!("expected parse error, got {err:?}"),
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[test]
+fn codec() {
+    let err = parse_error(StreamId::from_slice(BASE_CID.to_bytes()).unwrap_err());
+
+    assert_eq!(err.stage, ParseStage::Codec);
+    assert_eq!(err.offset, 0);
+    assert_eq!(err.expected, "streamid codec 206");
+    assert_eq!(err.actual, "1");
+    assert_eq!(err.input, hex(&BASE_CID.to_bytes()));
+}
+
+#[test]
+fn stream_type() {
+    let err = parse_error(StreamId::from_slice([0xce, 0x01, 0x80]).unwrap_err());
+    assert_eq!(err.stage, ParseStage::StreamType);
+    assert_eq!(err.offset, 2);
+    assert_eq!(err.expected, "varint");
+
+    let err = parse_error(StreamId::from_slice([0xce, 0x01, 0x09]).unwrap_err());
+    assert_eq!(err.stage, ParseStage::StreamType);
+    assert_eq!(err.offset, 2);
+    assert_eq!(err.expected, "known stream type");
+    assert_eq!(err.actual, "9");
+}
+
+#[test]
+fn genesis_cid() {
+    let err = parse_error(StreamId::from_slice(&STREAM_ID_BYTES[..10]).unwrap_err());
+
+    assert_eq!(err.stage, ParseStage::GenesisCid);
+    assert_eq!(err.offset, 3);
+    assert_eq!(err.expected, "CID");
+}
+
+#[test]
+fn commit_cid() {
+    let mut bytes = STREAM_ID_BYTES.clone();
+    bytes.extend([0x01, 0x71]);
+    let err = parse_error(CommitId::from_slice(&bytes).unwrap_err());
+
+    assert_eq!(err.stage, ParseStage::CommitCid);
+    assert_eq!(err.offset, STREAM_ID_BYTES.len());
+    assert_eq!(err.expected, "CID or zero commit");
+}
+
+#[test]
+fn trailing_data() {
+    let mut bytes = decode(STREAM_ID_WITH_COMMIT).unwrap().1;
+    let len = bytes.len();
+    bytes.extend([0, 0]);
+    let options = ParseOptions {
+        strict: true,
+        ..Default::default()
+    };
+    let err = parse_error(StreamRef::from_slice_with(&bytes, &options).unwrap_err());
+
+    assert_eq!(err.stage, ParseStage::TrailingData);
+    assert_eq!(err.offset, len);
+    assert_eq!(err.actual, "2 bytes");
+}
+
+#[test]
+fn kind() {
+    let err = parse_error(StreamId::from_str(STREAM_ID_WITH_COMMIT).unwrap_err());
+    assert_eq!(err.stage, ParseStage::Kind);
+    assert_eq!(err.expected, "StreamID");
+    assert_eq!(err.actual, "CommitID");
+    assert_eq!(err.input, STREAM_ID_WITH_COMMIT);
+    assert_eq!(err.offset, 0);
+
+    // Offsets into the input string, at the start of the multibase string.
+    #[cfg(feature = "regex")]
+    {
+        let url = format!("ceramic://{STREAM_ID_STRING}");
+        let err = parse_error(CommitId::from_str(&url).unwrap_err());
+        assert_eq!(err.stage, ParseStage::Kind);
+        assert_eq!(err.offset, "ceramic://".len());
+        assert_eq!(err.input, url);
+    }
+
+    let err = parse_error(CommitId::from_slice(&*STREAM_ID_BYTES).unwrap_err());
+    assert_eq!(err.stage, ParseStage::Kind);
+    assert_eq!(err.offset, STREAM_ID_BYTES.len());
+    assert_eq!(err.expected, "CommitID");
+    assert_eq!(err.actual, "StreamID");
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn url() {
+    let url = format!("ceramic://{STREAM_ID_STRING}/extra");
+    let err = parse_error(StreamId::from_str(&url).unwrap_err());
+    assert_eq!(err.stage, ParseStage::Url);
+    assert_eq!(
+        err.offset,
+        url.find('/').unwrap() + 2 + STREAM_ID_STRING.len()
+    );
+    assert_eq!(err.actual, "'/'");
+    assert_eq!(err.input, url);
+
+    let err = parse_error(StreamId::from_str("ceramic://").unwrap_err());
+    assert_eq!(err.stage, ParseStage::Url);
+    assert_eq!(err.offset, 10);
+    assert_eq!(err.expected, "multibase string");
+    assert_eq!(err.actual, "end of input");
+
+    let url = format!("{STREAM_ID_STRING}?commit=");
+    let err = parse_error(CommitId::from_str(&url).unwrap_err());
+    assert_eq!(err.stage, ParseStage::Url);
+    assert_eq!(err.offset, url.len());
+    assert_eq!(err.expected, "commit CID");
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn multibase() {
+    let url = format!("/ceramic/x{STREAM_ID_STRING}");
+    let err = parse_error(StreamId::from_str(&url).unwrap_err());
+    assert_eq!(err.stage, ParseStage::Multibase);
+    assert_eq!(err.offset, 9);
+
+    let options = ParseOptions {
+        base36_only: true,
+        ..Default::default()
+    };
+    let err = parse_error(StreamId::from_str_with(BASE_CID_STRING, &options).unwrap_err());
+    assert_eq!(err.stage, ParseStage::Multibase);
+    assert_eq!(err.expected, "base36");
+    assert_eq!(err.actual, "Base32Lower");
+}
+
+#[test]
+fn string_input() {
+    let err =
+        parse_error(StreamId::from_str(&format!("f{}", hex(&[0xce, 0x01, 0x09]))).unwrap_err());
+
+    assert_eq!(err.stage, ParseStage::StreamType);
+    assert_eq!(err.offset, 2);
+    assert_eq!(err.input, "fce0109");
+    assert_eq!(
+        err.to_string(),
+        "Invalid StreamRef fce0109: expected known stream type at type varint offset 2, found 9"
+    );
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn legacy_commit() {
+    let url = format!("/ceramic/{STREAM_ID_STRING}?commit=bogus");
+    let err = parse_error(CommitId::from_str(&url).unwrap_err());
+
+    assert_eq!(err.stage, ParseStage::CommitCid);
+    assert_eq!(err.offset, url.find('=').unwrap() + 1);
+    assert_eq!(err.expected, "CID or 0");
+}
@@ -0,0 +1,134 @@
+#![cfg(feature = "python")]
+
+use pyo3::{prelude::*, types::PyDict};
+use streamid::python::*;
+
+const STREAM_ID_STRING: &str = "kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s";
+const STREAM_ID_WITH_COMMIT: &str =
+  "k1dpgaqe3i64kjqcp801r3sn7ysi5i0k7nxvs7j351s7kewfzr3l7mdxnj7szwo4kr9mn2qki5nnj0cv836ythy1t1gya9s25cn1nexst3jxi5o3h6qprfyju";
+
+// Runs Python code with the classes importable from the `streamid` module.
+fn run(code: &str) {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let module = PyModule::new(py, "streamid").unwrap();
+        module.add_class::<PyStreamType>().unwrap();
+        module.add_class::<PyStreamId>().unwrap();
+        module.add_class::<PyCommitId>().unwrap();
+        module.add_class::<PyStreamRef>().unwrap();
+        py.import("sys")
+            .unwrap()
+            .getattr("modules")
+            .unwrap()
+            .set_item("streamid", module)
+            .unwrap();
+
+        let locals = PyDict::new(py);
+        locals.set_item("STREAM_ID", STREAM_ID_STRING).unwrap();
+        locals.set_item("COMMIT_ID", STREAM_ID_WITH_COMMIT).unwrap();
+        py.run(code, None, Some(locals))
+            .map_err(|err| err.print(py))
+            .unwrap();
+    });
+}
+
+#[test]
+fn stream_id() {
+    run(r#"
+from streamid import StreamId, StreamType
+
+stream_id = StreamId.from_str(STREAM_ID)
+assert str(stream_id) == STREAM_ID
+assert stream_id.stream_type == StreamType.TILE
+assert stream_id.stream_type.name == "tile"
+assert stream_id.cid == "bagcqcerakszw2vsovxznyp5gfnpdj4cqm2xiv76yd24wkjewhhykovorwo6a"
+assert stream_id.to_url() == "ceramic://" + STREAM_ID
+assert StreamId.from_bytes(stream_id.to_bytes()) == stream_id
+assert StreamId(StreamType.TILE, stream_id.cid) == stream_id
+
+try:
+    StreamId.from_str(COMMIT_ID)
+    assert False
+except ValueError:
+    pass
+"#);
+}
+
+#[test]
+fn commit_id() {
+    run(r#"
+from streamid import CommitId, StreamId
+
+commit_id = CommitId.from_str(COMMIT_ID)
+assert commit_id.commit == "bagjqcgzaday6dzalvmy5ady2m5a5legq5zrbsnlxfc2bfxej532ds7htpova"
+assert commit_id.to_base_id() == StreamId.from_str(STREAM_ID)
+assert StreamId.from_str(STREAM_ID).at_commit(commit_id.commit) == commit_id
+assert not commit_id.is_genesis()
+
+stream_id = StreamId.from_str(STREAM_ID)
+for genesis in [stream_id.at_commit("0"), stream_id.at_commit(0), commit_id.at_commit(0)]:
+    assert genesis.is_genesis()
+    assert genesis.commit == stream_id.cid
+    assert genesis == CommitId.from_str("/ceramic/" + STREAM_ID + "?commit=0")
+
+for commit in [1, "1", 0.0]:
+    try:
+        stream_id.at_commit(commit)
+        assert False
+    except (TypeError, ValueError):
+        pass
+"#);
+}
+
+#[test]
+fn stream_ref() {
+    run(r#"
+from streamid import CommitId, StreamId, StreamRef
+
+assert isinstance(StreamRef.from_str(STREAM_ID), StreamId)
+assert isinstance(StreamRef.from_str(COMMIT_ID), CommitId)
+"#);
+}
+
+#[test]
+fn hash_compare_pickle() {
+    run(r#"
+import pickle
+from streamid import CommitId, StreamId, StreamType
+
+stream_id = StreamId.from_str(STREAM_ID)
+commit_id = CommitId.from_str(COMMIT_ID)
+
+assert len({stream_id, StreamId.from_str(STREAM_ID)}) == 1
+assert sorted([commit_id.to_base_id(), stream_id]) == [stream_id, stream_id]
+assert pickle.loads(pickle.dumps(stream_id)) == stream_id
+assert pickle.loads(pickle.dumps(commit_id)) == commit_id
+assert pickle.loads(pickle.dumps(StreamType.MODEL)) == StreamType.MODEL
+assert StreamType.from_code(9).name is None
+
+unknown = StreamType.from_code(300)
+assert pickle.loads(pickle.dumps(unknown)) == unknown
+unknown_id = StreamId(unknown, stream_id.cid)
+assert pickle.loads(pickle.dumps(unknown_id)) == unknown_id
+unknown_commit_id = unknown_id.at_commit(commit_id.commit)
+assert pickle.loads(pickle.dumps(unknown_commit_id)) == unknown_commit_id
+
+try:
+    StreamId.from_bytes(unknown_id.to_bytes())
+    assert False
+except ValueError:
+    pass
+"#);
+}
+
+#[test]
+fn from_genesis() {
+    run(r#"
+from streamid import StreamId, StreamType
+
+genesis = {"header": {"controllers": ["did:3:kjz..."], "family": "IDX"}}
+stream_id = StreamId.from_genesis(StreamType.TILE, genesis)
+assert stream_id == StreamId.from_genesis(StreamType.TILE, genesis)
+assert stream_id != StreamId.from_genesis(StreamType.TILE, {"header": {}})
+"#);
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "rusqlite")]
+
+use std::str::FromStr;
+
+use rusqlite::{params, Connection};
+use streamid::{sql::Binary, *};
+
+const STREAM_ID_STRING: &str = "kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s";
+const STREAM_ID_WITH_COMMIT: &str =
+  "k1dpgaqe3i64kjqcp801r3sn7ysi5i0k7nxvs7j351s7kewfzr3l7mdxnj7szwo4kr9mn2qki5nnj0cv836ythy1t1gya9s25cn1nexst3jxi5o3h6qprfyju";
+
+fn connect() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute("CREATE TABLE ids (id TEXT, commit_id BLOB)", [])
+        .unwrap();
+    conn
+}
+
+#[test]
+fn text() {
+    let conn = connect();
+    let stream_id = StreamId::from_str(STREAM_ID_STRING).unwrap();
+    let commit_id = CommitId::from_str(STREAM_ID_WITH_COMMIT).unwrap();
+    conn.execute(
+        "INSERT INTO ids (id) VALUES (?1), (?2)",
+        params![stream_id, StreamRef::CommitId(commit_id.clone())],
+    )
+    .unwrap();
+
+    let text: String = conn
+        .query_row("SELECT id FROM ids", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(text, STREAM_ID_STRING);
+
+    let read: StreamId = conn
+        .query_row("SELECT id FROM ids WHERE id = ?1", [&stream_id], |row| {
+            row.get(0)
+        })
+        .unwrap();
+    assert_eq!(read, stream_id);
+
+    let read: CommitId = conn
+        .query_row("SELECT id FROM ids LIMIT 1 OFFSET 1", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(read, commit_id);
+}
+
+#[test]
+fn binary() {
+    let conn = connect();
+    let commit_id = CommitId::from_str(STREAM_ID_WITH_COMMIT).unwrap();
+    conn.execute(
+        "INSERT INTO ids (commit_id) VALUES (?1)",
+        [Binary(commit_id.clone())],
+    )
+    .unwrap();
+
+    let bytes: Vec<u8> = conn
+        .query_row("SELECT commit_id FROM ids", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(bytes, commit_id.to_bytes());
+
+    let read: Binary<StreamRef> = conn
+        .query_row("SELECT commit_id FROM ids", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(read.into_inner(), StreamRef::CommitId(commit_id));
+}
+
+#[test]
+fn invalid() {
+    let conn = connect();
+    conn.execute(
+        "INSERT INTO ids (id, commit_id) VALUES ('invalid', x'ce01')",
+        [],
+    )
+    .unwrap();
+
+    let err = conn
+        .query_row("SELECT id FROM ids", [], |row| row.get::<_, StreamId>(0))
+        .unwrap_err();
+    match err {
+        rusqlite::Error::FromSqlConversionFailure(_, _, err) => assert!(err.is::<Error>()),
+        err => panic!("unexpected error {err}"),
+    }
+
+    let err = conn
+        .query_row("SELECT commit_id FROM ids", [], |row| {
+            row.get::<_, Binary<CommitId>>(0)
+        })
+        .unwrap_err();
+    match err {
+        rusqlite::Error::FromSqlConversionFailure(_, _, err) => assert!(err.is::<Error>()),
+        err => panic!("unexpected error {err}"),
+    }
+
+    // Text is not read as bytes.
+    let err = conn
+        .query_row("SELECT id FROM ids", [], |row| {
+            row.get::<_, Binary<StreamId>>(0)
+        })
+        .unwrap_err();
+    assert!(matches!(err, rusqlite::Error::InvalidColumnType(..)));
+}
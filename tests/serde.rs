@@ -0,0 +1,76 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use streamid::*;
+
+const STREAM_ID_STRING: &str = "kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s";
+#[cfg(feature = "regex")]
+const STREAM_ID_URL: &str =
+    "ceramic://kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s";
+const STREAM_ID_WITH_COMMIT: &str =
+  "k1dpgaqe3i64kjqcp801r3sn7ysi5i0k7nxvs7j351s7kewfzr3l7mdxnj7szwo4kr9mn2qki5nnj0cv836ythy1t1gya9s25cn1nexst3jxi5o3h6qprfyju";
+#[cfg(feature = "regex")]
+const STREAM_ID_WITH_COMMIT_LEGACY: &str =
+    "/ceramic/kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s?commit=bagjqcgzaday6dzalvmy5ady2m5a5legq5zrbsnlxfc2bfxej532ds7htpova";
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Document {
+    stream_id: StreamId,
+    commit_id: CommitId,
+    stream_ref: StreamRef,
+}
+
+#[test]
+fn json_roundtrip() {
+    let document = Document {
+        stream_id: StreamId::from_str(STREAM_ID_STRING).unwrap(),
+        commit_id: CommitId::from_str(STREAM_ID_WITH_COMMIT).unwrap(),
+        stream_ref: StreamRef::from_str(STREAM_ID_STRING).unwrap(),
+    };
+
+    let json = serde_json::to_string(&document).unwrap();
+
+    assert_eq!(
+        json,
+        format!(
+            r#"{{"stream_id":"{STREAM_ID_STRING}","commit_id":"{STREAM_ID_WITH_COMMIT}","stream_ref":"{STREAM_ID_STRING}"}}"#
+        )
+    );
+    assert_eq!(serde_json::from_str::<Document>(&json).unwrap(), document);
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn json_from_url() {
+    let stream_id: StreamId = serde_json::from_str(&format!(r#""{STREAM_ID_URL}""#)).unwrap();
+    let commit_id: CommitId =
+        serde_json::from_str(&format!(r#""{STREAM_ID_WITH_COMMIT_LEGACY}""#)).unwrap();
+
+    assert_eq!(stream_id.to_string(), STREAM_ID_STRING);
+    assert_eq!(commit_id.to_string(), STREAM_ID_WITH_COMMIT);
+}
+
+#[test]
+fn json_err() {
+    assert!(serde_json::from_str::<StreamId>(&format!(r#""{STREAM_ID_WITH_COMMIT}""#)).is_err());
+    assert!(serde_json::from_str::<CommitId>(&format!(r#""{STREAM_ID_STRING}""#)).is_err());
+    assert!(serde_json::from_str::<StreamRef>(r#""garbage""#).is_err());
+}
+
+#[test]
+fn bincode_roundtrip() {
+    let stream_id = StreamId::from_str(STREAM_ID_STRING).unwrap();
+    let commit_id = CommitId::from_str(STREAM_ID_WITH_COMMIT).unwrap();
+
+    let bytes = bincode::serialize(&stream_id).unwrap();
+    assert!(bytes.ends_with(&stream_id.to_bytes()));
+    assert_eq!(bincode::deserialize::<StreamId>(&bytes).unwrap(), stream_id);
+
+    let bytes = bincode::serialize(&commit_id).unwrap();
+    assert!(bytes.ends_with(&commit_id.to_bytes()));
+    assert_eq!(bincode::deserialize::<CommitId>(&bytes).unwrap(), commit_id);
+    assert_eq!(
+        bincode::deserialize::<StreamRef>(&bytes).unwrap(),
+        StreamRef::CommitId(commit_id)
+    );
+}
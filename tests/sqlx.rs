@@ -0,0 +1,114 @@
+#![cfg(feature = "sqlx-sqlite")]
+
+use std::str::FromStr;
+
+use futures_executor::block_on;
+use sqlx::{Connection, SqliteConnection};
+use streamid::{sql::Binary, *};
+
+const STREAM_ID_STRING: &str = "kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s";
+const STREAM_ID_WITH_COMMIT: &str =
+  "k1dpgaqe3i64kjqcp801r3sn7ysi5i0k7nxvs7j351s7kewfzr3l7mdxnj7szwo4kr9mn2qki5nnj0cv836ythy1t1gya9s25cn1nexst3jxi5o3h6qprfyju";
+const STREAM_ID_WITH_0_COMMIT: &str =
+    "k3y52l7qbv1frxwipl4hp7e6jlu4f6u8upm2xv0irmedfkm5cnutmezzi3u7mytj4";
+
+async fn connect() -> SqliteConnection {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+    sqlx::query("CREATE TABLE ids (id TEXT, commit_id BLOB)")
+        .execute(&mut conn)
+        .await
+        .unwrap();
+    conn
+}
+
+#[test]
+fn text() {
+    block_on(async {
+        let mut conn = connect().await;
+        let stream_id = StreamId::from_str(STREAM_ID_STRING).unwrap();
+        let commit_id = CommitId::from_str(STREAM_ID_WITH_0_COMMIT).unwrap();
+
+        sqlx::query("INSERT INTO ids (id) VALUES (?), (?)")
+            .bind(&stream_id)
+            .bind(&commit_id)
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let rows: Vec<(String,)> = sqlx::query_as("SELECT id FROM ids")
+            .fetch_all(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(rows[0].0, STREAM_ID_STRING);
+        assert_eq!(rows[1].0, STREAM_ID_WITH_0_COMMIT);
+
+        let rows: Vec<(StreamRef,)> = sqlx::query_as("SELECT id FROM ids")
+            .fetch_all(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(rows[0].0, StreamRef::StreamId(stream_id.clone()));
+        assert_eq!(rows[1].0, StreamRef::CommitId(commit_id));
+
+        let (read,): (StreamId,) = sqlx::query_as("SELECT id FROM ids WHERE id = ?")
+            .bind(&stream_id)
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(read, stream_id);
+    });
+}
+
+#[test]
+fn binary() {
+    block_on(async {
+        let mut conn = connect().await;
+        let commit_id = CommitId::from_str(STREAM_ID_WITH_COMMIT).unwrap();
+
+        sqlx::query("INSERT INTO ids (commit_id) VALUES (?)")
+            .bind(Binary(commit_id.clone()))
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let (bytes,): (Vec<u8>,) = sqlx::query_as("SELECT commit_id FROM ids")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(bytes, commit_id.to_bytes());
+
+        let (read,): (Binary<CommitId>,) = sqlx::query_as("SELECT commit_id FROM ids")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(read.into_inner(), commit_id);
+    });
+}
+
+#[test]
+fn invalid() {
+    block_on(async {
+        let mut conn = connect().await;
+        sqlx::query("INSERT INTO ids (id, commit_id) VALUES ('invalid', x'ce01')")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let err = sqlx::query_as::<_, (StreamId,)>("SELECT id FROM ids")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap_err();
+        match err {
+            sqlx::Error::ColumnDecode { source, .. } => assert!(source.is::<Error>()),
+            err => panic!("unexpected error {err}"),
+        }
+
+        let err = sqlx::query_as::<_, (Binary<CommitId>,)>("SELECT commit_id FROM ids")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap_err();
+        match err {
+            sqlx::Error::ColumnDecode { source, .. } => assert!(source.is::<Error>()),
+            err => panic!("unexpected error {err}"),
+        }
+    });
+}
@@ -1,9 +1,15 @@
-use std::str::FromStr;
+use std::{collections::BTreeMap, str::FromStr};
 
-use cid::{multibase::decode, Cid};
+use cid::{
+    multibase::{decode, Base},
+    multihash::MultihashGeneric,
+    Cid,
+};
 use once_cell::sync::Lazy;
 use streamid::*;
 
+type Multihash = MultihashGeneric<64>;
+
 const BASE_CID_STRING: &str = "bagcqcerakszw2vsovxznyp5gfnpdj4cqm2xiv76yd24wkjewhhykovorwo6a";
 static BASE_CID: Lazy<Cid> = Lazy::new(|| Cid::from_str(BASE_CID_STRING).unwrap());
 const COMMIT_CID_STRING: &str = "bagjqcgzaday6dzalvmy5ady2m5a5legq5zrbsnlxfc2bfxej532ds7htpova";
@@ -86,6 +92,7 @@ fn from_str() {
     assert_eq!(stream_id.to_string(), STREAM_ID_STRING);
 }
 
+#[cfg(feature = "regex")]
 #[test]
 fn from_str_url() {
     let stream_id = StreamId::from_str(STREAM_ID_URL).unwrap();
@@ -95,6 +102,7 @@ fn from_str_url() {
     assert_eq!(stream_id.to_string(), STREAM_ID_STRING);
 }
 
+#[cfg(feature = "regex")]
 #[test]
 fn from_str_legacy_url() {
     let stream_id = StreamId::from_str(STREAM_ID_LEGACY).unwrap();
@@ -160,6 +168,21 @@ fn to_url() {
     assert_eq!(stream_id.to_url(), format!("ceramic://{STREAM_ID_STRING}"));
 }
 
+#[cfg(feature = "regex")]
+#[test]
+fn to_legacy_url() {
+    let stream_id = StreamId {
+        stream_type: StreamType::Tile,
+        cid: *BASE_CID,
+    };
+
+    assert_eq!(stream_id.to_legacy_url(), STREAM_ID_LEGACY);
+    assert_eq!(
+        StreamId::from_str(&stream_id.to_legacy_url()).unwrap(),
+        stream_id
+    );
+}
+
 #[test]
 fn eq() {
     let stream_id = StreamId {
@@ -180,3 +203,304 @@ fn eq() {
     assert_eq!(stream_id, stream_id2);
     assert_ne!(stream_id, stream_id3);
 }
+
+#[test]
+fn ord() {
+    let tile = StreamId {
+        stream_type: StreamType::Tile,
+        cid: *BASE_CID,
+    };
+    let caip10_link = StreamId {
+        stream_type: StreamType::Caip10Link,
+        cid: *BASE_CID,
+    };
+    let commit = StreamId {
+        stream_type: StreamType::Tile,
+        cid: *COMMIT_CID,
+    };
+
+    let mut stream_ids = vec![commit.clone(), caip10_link.clone(), tile.clone()];
+    stream_ids.sort();
+    assert_eq!(stream_ids, [tile.clone(), commit, caip10_link]);
+
+    for a in &stream_ids {
+        for b in &stream_ids {
+            assert_eq!(a.cmp(b) == std::cmp::Ordering::Equal, a == b);
+        }
+    }
+
+    // The order is the order of the encoded bytes, for type codes of several varint bytes,
+    // CIDv0 and CIDv1, codecs and hash codes of several varint bytes and digests of different
+    // lengths.
+    let cids = [
+        *BASE_CID,
+        *COMMIT_CID,
+        Cid::from_str("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG").unwrap(),
+        Cid::new_v1(0x55, Multihash::wrap(0x00, &[1, 2, 3]).unwrap()),
+        Cid::new_v1(0x55, Multihash::wrap(0x00, &[1, 2]).unwrap()),
+        Cid::new_v1(0x55, Multihash::wrap(0x00, &[2]).unwrap()),
+        Cid::new_v1(0x0129, Multihash::wrap(0x12, &[0; 32]).unwrap()),
+        Cid::new_v1(0x71, Multihash::wrap(0xb220, &[0; 32]).unwrap()),
+    ];
+    let stream_ids: Vec<_> = [0, 1, 127, 128, 129, 256, 300]
+        .into_iter()
+        .flat_map(|code| {
+            cids.iter().map(move |cid| StreamId {
+                stream_type: StreamType::from_code(code),
+                cid: *cid,
+            })
+        })
+        .collect();
+    let commit_ids: Vec<_> = stream_ids
+        .iter()
+        .flat_map(|stream_id| {
+            cids.iter()
+                .map(|cid| stream_id.at_commit(*cid))
+                .chain([CommitId {
+                    commit: None,
+                    ..stream_id.at_commit(*BASE_CID)
+                }])
+        })
+        .collect();
+    let stream_refs: Vec<_> = stream_ids
+        .iter()
+        .cloned()
+        .map(StreamRef::StreamId)
+        .chain(commit_ids.iter().cloned().map(StreamRef::CommitId))
+        .collect();
+
+    for a in &stream_ids {
+        for b in &stream_ids {
+            assert_eq!(a.cmp(b), a.to_bytes().cmp(&b.to_bytes()), "{a:?} {b:?}");
+        }
+    }
+    for a in &commit_ids {
+        for b in &commit_ids {
+            assert_eq!(a.cmp(b), a.to_bytes().cmp(&b.to_bytes()), "{a:?} {b:?}");
+        }
+    }
+    for a in &stream_refs {
+        for b in &stream_refs {
+            assert_eq!(a.cmp(b), a.to_bytes().cmp(&b.to_bytes()), "{a:?} {b:?}");
+        }
+    }
+}
+
+#[test]
+fn commits_in() {
+    let stream_id = StreamId::from_str(STREAM_ID_STRING).unwrap();
+    let other = StreamId {
+        stream_type: StreamType::Tile,
+        cid: *COMMIT_CID,
+    };
+    let genesis = CommitId::from_str(STREAM_ID_WITH_0_COMMIT).unwrap();
+    let commit = CommitId::from_str(STREAM_ID_WITH_COMMIT).unwrap();
+
+    let commits: BTreeMap<_, _> = [
+        (other.at_commit(*BASE_CID), 0),
+        (commit.clone(), 1),
+        (stream_id.at_commit(*BASE_CID), 2),
+        (genesis.clone(), 3),
+        (
+            CommitId {
+                commit: None,
+                ..other.at_commit(*BASE_CID)
+            },
+            4,
+        ),
+    ]
+    .into_iter()
+    .collect();
+    let values: Vec<_> = stream_id
+        .commits_in(&commits)
+        .map(|(_, value)| *value)
+        .collect();
+    assert_eq!(values, [3, 2, 1]);
+
+    let refs: BTreeMap<_, _> = [
+        (StreamRef::CommitId(commit), 1),
+        (StreamRef::StreamId(other.clone()), 2),
+        (StreamRef::CommitId(genesis), 3),
+        (StreamRef::StreamId(stream_id.clone()), 4),
+        (StreamRef::CommitId(other.at_commit(*BASE_CID)), 5),
+    ]
+    .into_iter()
+    .collect();
+    let values: Vec<_> = stream_id.refs_in(&refs).map(|(_, value)| *value).collect();
+    assert_eq!(values, [4, 3, 1]);
+    let values: Vec<_> = other.refs_in(&refs).map(|(_, value)| *value).collect();
+    assert_eq!(values, [2, 5]);
+}
+
+#[test]
+fn unknown_stream_type() {
+    let stream_id = StreamId {
+        stream_type: StreamType::from_code(300),
+        cid: *BASE_CID,
+    };
+    let options = ParseOptions {
+        allow_unknown_stream_types: true,
+        ..Default::default()
+    };
+
+    assert!(StreamId::from_slice(stream_id.to_bytes()).is_err());
+    assert!(StreamId::from_str(&stream_id.to_string()).is_err());
+    assert_eq!(
+        StreamId::from_slice_with(stream_id.to_bytes(), &options).unwrap(),
+        stream_id
+    );
+    assert_eq!(
+        StreamId::from_str_with(&stream_id.to_string(), &options).unwrap(),
+        stream_id
+    );
+}
+
+#[test]
+fn write_bytes() {
+    let stream_id = StreamId {
+        stream_type: StreamType::Tile,
+        cid: *BASE_CID,
+    };
+    let mut bytes = vec![];
+
+    assert_eq!(
+        stream_id.write_bytes(&mut bytes).unwrap(),
+        stream_id.encoded_len()
+    );
+    assert_eq!(bytes, *STREAM_ID_BYTES);
+}
+
+#[test]
+fn read_bytes() {
+    let bytes = [STREAM_ID_BYTES.as_slice(), &[1, 2, 3]].concat();
+    let mut reader = bytes.as_slice();
+
+    let stream_id = StreamId::read_bytes(&mut reader).unwrap();
+
+    assert_eq!(stream_id.to_string(), STREAM_ID_STRING);
+    assert_eq!(reader, &[1, 2, 3]);
+
+    // Following bytes are not mistaken for a commit.
+    for next in [0, 1] {
+        let bytes = [STREAM_ID_BYTES.as_slice(), &[next]].concat();
+        let mut reader = bytes.as_slice();
+        assert_eq!(
+            StreamId::read_bytes(&mut reader).unwrap().to_string(),
+            STREAM_ID_STRING
+        );
+        assert_eq!(reader, &[next]);
+    }
+}
+
+#[test]
+fn to_string_of_base() {
+    let stream_id = StreamId {
+        stream_type: StreamType::Tile,
+        cid: *BASE_CID,
+    };
+
+    assert_eq!(
+        stream_id.to_string_of_base(Base::Base36Lower),
+        STREAM_ID_STRING
+    );
+
+    for base in [
+        Base::Base32Lower,
+        Base::Base58Btc,
+        Base::Base64Url,
+        Base::Base16Lower,
+    ] {
+        let s = stream_id.to_string_of_base(base);
+
+        assert_eq!(StreamId::from_str(&s).unwrap(), stream_id);
+        #[cfg(feature = "regex")]
+        assert_eq!(
+            StreamId::from_str(&format!("ceramic://{s}")).unwrap(),
+            stream_id
+        );
+    }
+}
+
+#[test]
+fn from_str_base36_only() {
+    let stream_id = StreamId {
+        stream_type: StreamType::Tile,
+        cid: *BASE_CID,
+    };
+    let options = ParseOptions {
+        base36_only: true,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        StreamId::from_str_with(STREAM_ID_STRING, &options).unwrap(),
+        stream_id
+    );
+    assert!(
+        StreamId::from_str_with(&stream_id.to_string_of_base(Base::Base58Btc), &options).is_err()
+    );
+}
+
+#[test]
+fn from_str_unsupported_base() {
+    let stream_id = StreamId {
+        stream_type: StreamType::Tile,
+        cid: *BASE_CID,
+    };
+
+    assert!(StreamId::from_str(&stream_id.to_string_of_base(Base::Base32Upper)).is_err());
+    assert!(StreamId::from_str(&stream_id.to_string_of_base(Base::Base2)).is_err());
+}
+
+#[test]
+fn from_str_strict() {
+    let stream_id = StreamId {
+        stream_type: StreamType::Tile,
+        cid: *BASE_CID,
+    };
+    let options = ParseOptions {
+        strict: true,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        StreamId::from_str_with(STREAM_ID_STRING, &options).unwrap(),
+        stream_id
+    );
+    let mut non_canonical = vec![
+        stream_id.to_string_of_base(Base::Base32Lower),
+        stream_id.to_string_of_base(Base::Base58Btc),
+    ];
+    if cfg!(feature = "regex") {
+        non_canonical.extend([STREAM_ID_URL.to_string(), STREAM_ID_LEGACY.to_string()]);
+    }
+    for s in non_canonical {
+        assert_eq!(StreamId::from_str(&s).unwrap(), stream_id, "{s}");
+        assert!(StreamId::from_str_with(&s, &options).is_err(), "{s}");
+    }
+}
+
+#[test]
+fn from_bytes_strict() {
+    let options = ParseOptions {
+        strict: true,
+        ..Default::default()
+    };
+    let stream_id = StreamId::from_slice(&*STREAM_ID_BYTES).unwrap();
+    let redundant = stream_id.at_commit(stream_id.cid).to_bytes();
+    let commit = stream_id.at_commit(*COMMIT_CID).to_bytes();
+    let trailing = [commit.as_slice(), &[1, 2, 3]].concat();
+
+    assert!(StreamId::from_slice_with(&*STREAM_ID_BYTES, &options).is_ok());
+    for bytes in [redundant, trailing] {
+        assert!(StreamRef::from_slice(&bytes).is_ok());
+        assert!(StreamRef::from_slice_with(&bytes, &options).is_err());
+    }
+
+    // Non-minimal varints are rejected in every mode.
+    let mut non_minimal = STREAM_ID_BYTES.clone();
+    // Stream type 0 encoded as a two bytes varint
+    non_minimal.splice(2..3, [0x80, 0x00]);
+    assert!(StreamId::from_slice(&non_minimal).is_err());
+    assert!(StreamId::from_slice_with(non_minimal, &options).is_err());
+}
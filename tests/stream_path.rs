@@ -0,0 +1,265 @@
+#[cfg(feature = "ipld")]
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use streamid::*;
+
+const STREAM_ID_STRING: &str = "kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s";
+const STREAM_ID_WITH_COMMIT: &str =
+  "k1dpgaqe3i64kjqcp801r3sn7ysi5i0k7nxvs7j351s7kewfzr3l7mdxnj7szwo4kr9mn2qki5nnj0cv836ythy1t1gya9s25cn1nexst3jxi5o3h6qprfyju";
+const COMMIT_CID_STRING: &str = "bagjqcgzaday6dzalvmy5ady2m5a5legq5zrbsnlxfc2bfxej532ds7htpova";
+
+fn stream_ref() -> StreamRef {
+    StreamRef::from_str(STREAM_ID_STRING).unwrap()
+}
+
+#[test]
+fn new() {
+    let path = StreamPath::new(stream_ref(), "").unwrap();
+    assert!(path.tokens.is_empty());
+    assert_eq!(path.to_pointer(), "");
+
+    let path = StreamPath::new(stream_ref(), "/").unwrap();
+    assert_eq!(path.tokens, [""]);
+    assert_eq!(path.to_pointer(), "/");
+
+    // RFC 6901 section 5 examples
+    for (pointer, token) in [
+        ("/a~1b", "a/b"),
+        ("/c%d", "c%d"),
+        ("/m~0n", "m~n"),
+        ("/~01", "~1"),
+        ("/ ", " "),
+    ] {
+        let path = StreamPath::new(stream_ref(), pointer).unwrap();
+        assert_eq!(path.tokens, [token]);
+        assert_eq!(path.to_pointer(), pointer);
+    }
+
+    let path = StreamPath::new(stream_ref(), "/profile/name/").unwrap();
+    assert_eq!(path.tokens, ["profile", "name", ""]);
+}
+
+#[test]
+fn new_invalid() {
+    let parse_error = |pointer: &str| match StreamPath::new(stream_ref(), pointer).unwrap_err() {
+        Error::Parse(err) => err,
+        err => panic!("unexpected error {err}"),
+    };
+
+    let err = parse_error("profile");
+    assert_eq!((err.stage, err.offset), (ParseStage::Url, 0));
+    assert_eq!(err.input, "profile");
+
+    let err = parse_error("/profile/a~2");
+    assert_eq!((err.stage, err.offset), (ParseStage::Url, 10));
+    assert_eq!(err.actual, "~2");
+
+    let err = parse_error("/a~");
+    assert_eq!((err.stage, err.offset), (ParseStage::Url, 2));
+    assert_eq!(err.actual, "~");
+
+    // Dot segments have no URL form.
+    let err = parse_error("/a/..");
+    assert_eq!((err.stage, err.offset), (ParseStage::Url, 3));
+    assert_eq!(err.actual, "..");
+    assert!(StreamPath::new(stream_ref(), "/.").is_err());
+}
+
+#[test]
+fn parse() {
+    let path = StreamPath::from_str(&format!("ceramic://{STREAM_ID_STRING}")).unwrap();
+    assert_eq!(path.stream_ref, stream_ref());
+    assert!(path.tokens.is_empty());
+
+    let path = StreamPath::from_str(&format!(
+        "ceramic://{STREAM_ID_STRING}/profile/a~1b/m~0n/%C3%A9t%C3%A9%20~01"
+    ))
+    .unwrap();
+    assert_eq!(path.tokens, ["profile", "a/b", "m~n", "été ~1"]);
+    assert_eq!(path.to_pointer(), "/profile/a~1b/m~0n/été ~01");
+
+    let path = StreamPath::from_str(&format!(
+        "ceramic://{STREAM_ID_STRING}/profile?commit={COMMIT_CID_STRING}"
+    ))
+    .unwrap();
+    assert_eq!(
+        path.stream_ref,
+        StreamRef::from_str(STREAM_ID_WITH_COMMIT).unwrap()
+    );
+    assert_eq!(path.tokens, ["profile"]);
+}
+
+#[test]
+fn parse_invalid() {
+    let parse_error = |s: String| match StreamPath::from_str(&s).unwrap_err() {
+        Error::Parse(err) => err,
+        err => panic!("unexpected error {err}"),
+    };
+
+    let err = parse_error(format!("ceramic://{STREAM_ID_STRING}/profile?sync=1"));
+    assert_eq!(err.stage, ParseStage::Url);
+    assert_eq!(err.offset, 10 + STREAM_ID_STRING.len() + 8);
+    assert_eq!(err.actual, "sync");
+
+    let err = parse_error(format!("ceramic://{STREAM_ID_STRING}/profile/%FF"));
+    assert_eq!(err.stage, ParseStage::Url);
+    assert_eq!(err.offset, 10 + STREAM_ID_STRING.len() + 9);
+    assert_eq!(err.actual, "%FF");
+
+    let err = parse_error(format!("ceramic://{STREAM_ID_STRING}/a%2"));
+    assert_eq!(err.offset, 10 + STREAM_ID_STRING.len() + 1);
+
+    // Only hex digits, `u8::from_str_radix` would take the sign.
+    let err = parse_error(format!("ceramic://{STREAM_ID_STRING}/%+f"));
+    assert_eq!(err.offset, 10 + STREAM_ID_STRING.len() + 1);
+    assert_eq!(err.actual, "%+f");
+
+    let err = parse_error(format!("ceramic://{STREAM_ID_STRING}/%2E%2E"));
+    assert_eq!(err.offset, 10 + STREAM_ID_STRING.len() + 1);
+    assert_eq!(err.actual, "%2E%2E");
+
+    let err = parse_error(format!("ceramic://{STREAM_ID_STRING}/a/b~2"));
+    assert_eq!(err.offset, 10 + STREAM_ID_STRING.len() + 4);
+    assert_eq!(err.actual, "~2");
+
+    let err = parse_error(format!("ceramic://{STREAM_ID_STRING}/a/%C3%A9~2"));
+    assert_eq!(err.offset, 10 + STREAM_ID_STRING.len() + 9);
+    assert_eq!(err.actual, "~2");
+
+    let err = parse_error(format!("ceramic://{STREAM_ID_STRING}/a#b"));
+    assert_eq!(err.stage, ParseStage::Url);
+}
+
+#[test]
+fn round_trip() {
+    for pointer in [
+        "",
+        "/",
+        "/profile/name",
+        "/a~1b/m~0n",
+        "/été/a b?c#d%e",
+        "/...",
+        "/.a/..b",
+    ] {
+        let path = StreamPath::new(stream_ref(), pointer).unwrap();
+        let s = path.to_string();
+        assert!(s.starts_with(&format!("ceramic://{STREAM_ID_STRING}")));
+        assert_eq!(StreamPath::from_str(&s).unwrap(), path, "{s}");
+    }
+
+    let path = StreamPath::new(stream_ref(), "/a b/...").unwrap();
+    assert_eq!(
+        path.to_string(),
+        format!("ceramic://{STREAM_ID_STRING}/a%20b/...")
+    );
+
+    let path = StreamPath::new(StreamRef::from_str(STREAM_ID_WITH_COMMIT).unwrap(), "/x").unwrap();
+    assert_eq!(
+        path.to_string(),
+        format!("ceramic://{STREAM_ID_WITH_COMMIT}/x")
+    );
+}
+
+#[cfg(feature = "ipld")]
+fn content() -> Ipld {
+    Ipld::Map(BTreeMap::from([
+        (
+            "profile".to_string(),
+            Ipld::Map(BTreeMap::from([
+                ("name".to_string(), Ipld::String("Alice".into())),
+                ("a/b".to_string(), Ipld::Integer(1)),
+                ("".to_string(), Ipld::Bool(true)),
+            ])),
+        ),
+        (
+            "tags".to_string(),
+            Ipld::List(vec![Ipld::String("a".into()), Ipld::String("b".into())]),
+        ),
+        (
+            "link".to_string(),
+            Ipld::Link(stream_ref().to_base_id().cid),
+        ),
+    ]))
+}
+
+#[cfg(feature = "ipld")]
+#[test]
+fn resolve() {
+    let content = content();
+    let resolve = |pointer: &str| {
+        StreamPath::new(stream_ref(), pointer)
+            .unwrap()
+            .resolve(&content)
+    };
+
+    assert_eq!(resolve(""), Ok(&content));
+    assert_eq!(resolve("/profile/name"), Ok(&Ipld::String("Alice".into())));
+    assert_eq!(resolve("/profile/a~1b"), Ok(&Ipld::Integer(1)));
+    assert_eq!(resolve("/profile/"), Ok(&Ipld::Bool(true)));
+    assert_eq!(resolve("/tags/1"), Ok(&Ipld::String("b".into())));
+}
+
+#[cfg(feature = "ipld")]
+#[test]
+fn resolve_invalid() {
+    let content = content();
+    let resolve = |pointer: &str| {
+        StreamPath::new(stream_ref(), pointer)
+            .unwrap()
+            .resolve(&content)
+            .unwrap_err()
+    };
+
+    assert_eq!(
+        resolve("/profile/age"),
+        ResolveError::MissingKey {
+            pointer: "/profile/age".to_string()
+        }
+    );
+    assert_eq!(
+        resolve("/tags/2"),
+        ResolveError::IndexOutOfBounds {
+            pointer: "/tags/2".to_string(),
+            len: 2
+        }
+    );
+    assert_eq!(
+        resolve("/tags/-"),
+        ResolveError::IndexOutOfBounds {
+            pointer: "/tags/-".to_string(),
+            len: 2
+        }
+    );
+    assert_eq!(
+        resolve("/tags/99999999999999999999999"),
+        ResolveError::IndexOutOfBounds {
+            pointer: "/tags/99999999999999999999999".to_string(),
+            len: 2
+        }
+    );
+    for index in ["01", "+1", "x", ""] {
+        assert_eq!(
+            resolve(&format!("/tags/{index}")),
+            ResolveError::InvalidIndex {
+                pointer: format!("/tags/{index}")
+            }
+        );
+    }
+    assert_eq!(
+        resolve("/profile/name/first"),
+        ResolveError::NotAContainer {
+            pointer: "/profile/name/first".to_string(),
+            kind: "string"
+        }
+    );
+    let err = resolve("/link/a~1b");
+    assert_eq!(
+        err,
+        ResolveError::NotAContainer {
+            pointer: "/link/a~1b".to_string(),
+            kind: "link"
+        }
+    );
+    assert_eq!(err.to_string(), "Cannot index link at /link/a~1b");
+}
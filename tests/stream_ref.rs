@@ -1,3 +1,13 @@
+use std::str::FromStr;
+
+use streamid::*;
+
+const STREAM_ID_STRING: &str = "kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s";
+const STREAM_ID_WITH_COMMIT: &str =
+  "k1dpgaqe3i64kjqcp801r3sn7ysi5i0k7nxvs7j351s7kewfzr3l7mdxnj7szwo4kr9mn2qki5nnj0cv836ythy1t1gya9s25cn1nexst3jxi5o3h6qprfyju";
+const STREAM_ID_WITH_0_COMMIT: &str =
+    "k3y52l7qbv1frxwipl4hp7e6jlu4f6u8upm2xv0irmedfkm5cnutmezzi3u7mytj4";
+
 // import { StreamRef } from '../stream-ref.js'
 // import { StreamID } from '../stream-id.js'
 // import { CommitID } from '../commit-id.js'
@@ -45,3 +55,149 @@
 //     expect(() => StreamRef.from(new Uint8Array([1, 2, 3]))).toThrow()
 //   })
 // })
+
+#[test]
+fn read_write_bytes() {
+    let stream_refs = [
+        StreamRef::from_str(STREAM_ID_WITH_COMMIT).unwrap(),
+        StreamRef::from_str(STREAM_ID_WITH_0_COMMIT).unwrap(),
+        StreamRef::from_str(STREAM_ID_STRING).unwrap(),
+    ];
+
+    let mut bytes = vec![];
+    for stream_ref in &stream_refs {
+        let written = stream_ref.write_bytes(&mut bytes).unwrap();
+        assert_eq!(written, stream_ref.encoded_len());
+        assert_eq!(written, stream_ref.to_bytes().len());
+    }
+    bytes.push(0xff);
+
+    // The encoding does not say whether a commit follows, the reader must know.
+    let mut reader = bytes.as_slice();
+    for stream_ref in &stream_refs {
+        let read = match stream_ref {
+            StreamRef::CommitId(_) => CommitId::read_bytes(&mut reader).map(StreamRef::CommitId),
+            StreamRef::StreamId(_) => StreamId::read_bytes(&mut reader).map(StreamRef::StreamId),
+        };
+        assert_eq!(&read.unwrap(), stream_ref);
+    }
+    assert_eq!(reader, &[0xff]);
+}
+
+#[test]
+fn read_mixed_records() {
+    let stream_refs = [
+        StreamRef::from_str(STREAM_ID_STRING).unwrap(),
+        StreamRef::from_str(STREAM_ID_WITH_COMMIT).unwrap(),
+        StreamRef::from_str(STREAM_ID_STRING).unwrap(),
+        StreamRef::from_str(STREAM_ID_WITH_0_COMMIT).unwrap(),
+    ];
+
+    // Records of a kind byte, the reference and a length-prefixed payload.
+    let mut bytes = vec![];
+    for (i, stream_ref) in stream_refs.iter().enumerate() {
+        bytes.push(matches!(stream_ref, StreamRef::CommitId(_)).into());
+        stream_ref.write_bytes(&mut bytes).unwrap();
+        bytes.extend([1, i as u8]);
+    }
+
+    let mut reader = bytes.as_slice();
+    for (i, stream_ref) in stream_refs.iter().enumerate() {
+        let (kind, rest) = reader.split_first().unwrap();
+        reader = rest;
+        let read = match kind {
+            0 => StreamId::read_bytes(&mut reader).map(StreamRef::StreamId),
+            _ => CommitId::read_bytes(&mut reader).map(StreamRef::CommitId),
+        };
+        assert_eq!(&read.unwrap(), stream_ref);
+
+        let (len, rest) = reader.split_first().unwrap();
+        let (payload, rest) = rest.split_at((*len).into());
+        assert_eq!(payload, [i as u8]);
+        reader = rest;
+    }
+    assert!(reader.is_empty());
+}
+
+#[test]
+fn ord() {
+    let stream_id = StreamRef::from_str(STREAM_ID_STRING).unwrap();
+    let genesis = StreamRef::from_str(STREAM_ID_WITH_0_COMMIT).unwrap();
+    let commit = StreamRef::from_str(STREAM_ID_WITH_COMMIT).unwrap();
+
+    let mut stream_refs = vec![commit.clone(), genesis.clone(), stream_id.clone()];
+    stream_refs.sort();
+    assert_eq!(stream_refs, [stream_id.clone(), genesis, commit]);
+
+    for a in &stream_refs {
+        for b in &stream_refs {
+            assert_eq!(a.cmp(b) == std::cmp::Ordering::Equal, a == b);
+        }
+    }
+
+    // The StreamId sorts before the CommitIds of its stream only.
+    let other = StreamRef::StreamId(StreamId {
+        stream_type: StreamType::Model,
+        ..stream_id.to_base_id()
+    });
+    assert!(stream_refs.iter().all(|stream_ref| *stream_ref < other));
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn to_legacy_url() {
+    for s in [
+        STREAM_ID_STRING,
+        STREAM_ID_WITH_COMMIT,
+        STREAM_ID_WITH_0_COMMIT,
+    ] {
+        let stream_ref = StreamRef::from_str(s).unwrap();
+        let legacy_url = stream_ref.to_legacy_url();
+
+        assert!(legacy_url.starts_with(&format!("/ceramic/{STREAM_ID_STRING}")));
+        assert_eq!(StreamRef::from_str(&legacy_url).unwrap(), stream_ref);
+    }
+}
+
+// Implements only the required methods of `StreamRefExt`.
+struct Minimal(StreamRef);
+
+impl StreamRefExt for Minimal {
+    fn stream_type(&self) -> StreamType {
+        self.0.stream_type()
+    }
+
+    fn cid(&self) -> &Cid {
+        self.0.cid()
+    }
+
+    fn at_commit(&self, commit: Cid) -> CommitId {
+        self.0.at_commit(commit)
+    }
+
+    fn to_base_id(&self) -> StreamId {
+        self.0.to_base_id()
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_bytes()
+    }
+}
+
+#[test]
+fn provided_methods() {
+    for s in [
+        STREAM_ID_STRING,
+        STREAM_ID_WITH_COMMIT,
+        STREAM_ID_WITH_0_COMMIT,
+    ] {
+        let stream_ref = StreamRef::from_str(s).unwrap();
+        let minimal = Minimal(stream_ref.clone());
+
+        assert_eq!(minimal.to_legacy_url(), stream_ref.to_legacy_url());
+        assert_eq!(minimal.encoded_len(), stream_ref.encoded_len());
+        let mut bytes = vec![];
+        assert_eq!(minimal.write_bytes(&mut bytes).unwrap(), bytes.len());
+        assert_eq!(bytes, stream_ref.to_bytes());
+    }
+}
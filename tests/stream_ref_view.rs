@@ -0,0 +1,81 @@
+use std::str::FromStr;
+
+use cid::{multibase::decode, Cid};
+use once_cell::sync::Lazy;
+use streamid::*;
+
+const BASE_CID_STRING: &str = "bagcqcerakszw2vsovxznyp5gfnpdj4cqm2xiv76yd24wkjewhhykovorwo6a";
+static BASE_CID: Lazy<Cid> = Lazy::new(|| Cid::from_str(BASE_CID_STRING).unwrap());
+const COMMIT_CID_STRING: &str = "bagjqcgzaday6dzalvmy5ady2m5a5legq5zrbsnlxfc2bfxej532ds7htpova";
+static COMMIT_CID: Lazy<Cid> = Lazy::new(|| Cid::from_str(COMMIT_CID_STRING).unwrap());
+
+const STREAM_ID_STRING: &str = "kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s";
+static STREAM_ID_BYTES: Lazy<Vec<u8>> = Lazy::new(|| decode(STREAM_ID_STRING).unwrap().1);
+const STREAM_ID_WITH_COMMIT: &str =
+  "k1dpgaqe3i64kjqcp801r3sn7ysi5i0k7nxvs7j351s7kewfzr3l7mdxnj7szwo4kr9mn2qki5nnj0cv836ythy1t1gya9s25cn1nexst3jxi5o3h6qprfyju";
+static STREAM_ID_WITH_COMMIT_BYTES: Lazy<Vec<u8>> =
+    Lazy::new(|| decode(STREAM_ID_WITH_COMMIT).unwrap().1);
+const STREAM_ID_WITH_0_COMMIT: &str =
+    "k3y52l7qbv1frxwipl4hp7e6jlu4f6u8upm2xv0irmedfkm5cnutmezzi3u7mytj4";
+static STREAM_ID_WITH_0_COMMIT_BYTES: Lazy<Vec<u8>> =
+    Lazy::new(|| decode(STREAM_ID_WITH_0_COMMIT).unwrap().1);
+
+#[test]
+fn stream_id() {
+    let view = StreamRefView::from_slice(&STREAM_ID_BYTES).unwrap();
+
+    assert_eq!(view.stream_type(), StreamType::Tile);
+    assert_eq!(view.cid_bytes(), BASE_CID.to_bytes());
+    assert!(!view.is_commit_id());
+    assert_eq!(view.commit_bytes(), None);
+    assert_eq!(view.as_bytes(), STREAM_ID_BYTES.as_slice());
+    assert_eq!(
+        view.to_owned(),
+        StreamRef::from_slice(&*STREAM_ID_BYTES).unwrap()
+    );
+}
+
+#[test]
+fn commit_id() {
+    let view = StreamRefView::from_slice(&STREAM_ID_WITH_COMMIT_BYTES).unwrap();
+
+    assert_eq!(view.stream_type(), StreamType::Tile);
+    assert_eq!(view.cid_bytes(), BASE_CID.to_bytes());
+    assert!(view.is_commit_id());
+    assert_eq!(view.commit_bytes(), Some(COMMIT_CID.to_bytes().as_slice()));
+    assert_eq!(
+        view.to_owned(),
+        StreamRef::from_slice(&*STREAM_ID_WITH_COMMIT_BYTES).unwrap()
+    );
+}
+
+#[test]
+fn commit_id_at_genesis() {
+    let view = StreamRefView::from_slice(&STREAM_ID_WITH_0_COMMIT_BYTES).unwrap();
+
+    assert_eq!(view.cid_bytes(), BASE_CID.to_bytes());
+    assert!(view.is_commit_id());
+    assert_eq!(view.commit_bytes(), None);
+    assert_eq!(
+        view.to_owned(),
+        StreamRef::from_slice(&*STREAM_ID_WITH_0_COMMIT_BYTES).unwrap()
+    );
+}
+
+#[test]
+fn from_slice_err() {
+    assert!(StreamRefView::from_slice(&BASE_CID.to_bytes()).is_err());
+    assert!(StreamRefView::from_slice(&[1, 2, 3]).is_err());
+    assert!(StreamRefView::from_slice(&STREAM_ID_BYTES[..10]).is_err());
+}
+
+#[test]
+fn cmp() {
+    let stream_id = StreamRefView::from_slice(&STREAM_ID_BYTES).unwrap();
+    let commit_id = StreamRefView::from_slice(&STREAM_ID_WITH_COMMIT_BYTES).unwrap();
+    let bytes = STREAM_ID_BYTES.clone();
+
+    assert_eq!(stream_id, StreamRefView::from_slice(&bytes).unwrap());
+    assert_ne!(stream_id, commit_id);
+    assert!(stream_id < commit_id);
+}
@@ -0,0 +1,115 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use streamid::*;
+
+#[test]
+fn from_code() {
+    assert_eq!(StreamType::from_code(0), StreamType::Tile);
+    assert_eq!(StreamType::from_code(3), StreamType::Mid);
+    assert!(matches!(
+        StreamType::from_code(9),
+        StreamType::Unknown(unknown) if unknown.code() == 9
+    ));
+    assert_eq!(StreamType::from_code(9).code(), 9);
+    assert_eq!(u64::from(StreamType::Model), 2);
+}
+
+#[test]
+fn try_from() {
+    assert_eq!(StreamType::try_from(1u64).unwrap(), StreamType::Caip10Link);
+    assert!(StreamType::try_from(9u64).is_err());
+
+    assert_eq!(StreamType::try_from(3u8).unwrap(), StreamType::Mid);
+    assert_eq!(StreamType::try_from(9u8).unwrap_err().number, 9);
+    assert_eq!(u8::try_from(StreamType::Unloadable).unwrap(), 4);
+    assert_eq!(u8::try_from(StreamType::from_code(200)).unwrap(), 200);
+    assert!(u8::try_from(StreamType::from_code(300)).is_err());
+}
+
+#[test]
+fn to_string() {
+    assert_eq!(StreamType::Tile.to_string(), "tile");
+    assert_eq!(StreamType::Caip10Link.to_string(), "caip10-link");
+    assert_eq!(StreamType::Mid.to_string(), "MID");
+    assert_eq!(StreamType::from_code(9).to_string(), "9");
+}
+
+#[test]
+fn from_str() {
+    assert_eq!(StreamType::from_str("model").unwrap(), StreamType::Model);
+    assert_eq!(
+        StreamType::from_str("UNLOADABLE").unwrap(),
+        StreamType::Unloadable
+    );
+    assert_eq!(StreamType::from_str("3").unwrap(), StreamType::Mid);
+    assert_eq!(StreamType::from_str("9").unwrap(), StreamType::from_code(9));
+    assert!(StreamType::from_str("document").is_err());
+}
+
+#[test]
+fn serde() {
+    assert_eq!(serde_json::to_string(&StreamType::Mid).unwrap(), r#""MID""#);
+    assert_eq!(
+        serde_json::to_string(&StreamType::from_code(9)).unwrap(),
+        "9"
+    );
+    assert_eq!(
+        serde_json::from_str::<StreamType>(r#""tile""#).unwrap(),
+        StreamType::Tile
+    );
+    assert_eq!(
+        serde_json::from_str::<StreamType>("9").unwrap(),
+        StreamType::from_code(9)
+    );
+
+    let bytes = bincode::serialize(&StreamType::from_code(300)).unwrap();
+    assert_eq!(
+        bincode::deserialize::<StreamType>(&bytes).unwrap(),
+        StreamType::from_code(300)
+    );
+}
+
+// The closed enum `StreamType` derived serde for before unknown types were kept.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum DerivedStreamType {
+    #[serde(rename = "tile")]
+    Tile,
+    #[serde(rename = "caip10-link")]
+    Caip10Link,
+    #[serde(rename = "model")]
+    Model,
+    #[serde(rename = "MID")]
+    Mid,
+    #[serde(rename = "UNLOADABLE")]
+    Unloadable,
+}
+
+#[test]
+fn serde_derived_wire_format() {
+    for (stream_type, derived) in [
+        (StreamType::Tile, DerivedStreamType::Tile),
+        (StreamType::Caip10Link, DerivedStreamType::Caip10Link),
+        (StreamType::Model, DerivedStreamType::Model),
+        (StreamType::Mid, DerivedStreamType::Mid),
+        (StreamType::Unloadable, DerivedStreamType::Unloadable),
+    ] {
+        let bytes = bincode::serialize(&derived).unwrap();
+        assert_eq!(bincode::serialize(&stream_type).unwrap(), bytes);
+        assert_eq!(
+            bincode::deserialize::<StreamType>(&bytes).unwrap(),
+            stream_type
+        );
+
+        let json = serde_json::to_string(&derived).unwrap();
+        assert_eq!(serde_json::to_string(&stream_type).unwrap(), json);
+        assert_eq!(
+            serde_json::from_str::<StreamType>(&json).unwrap(),
+            stream_type
+        );
+    }
+
+    // Unknown types are a sixth variant, which older readers reject.
+    let bytes = bincode::serialize(&StreamType::from_code(9)).unwrap();
+    assert!(bincode::deserialize::<DerivedStreamType>(&bytes).is_err());
+}
@@ -0,0 +1,54 @@
+#![cfg(all(feature = "wasm", target_arch = "wasm32"))]
+
+use streamid::wasm::*;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const BASE_CID_STRING: &str = "bagcqcerakszw2vsovxznyp5gfnpdj4cqm2xiv76yd24wkjewhhykovorwo6a";
+const COMMIT_CID_STRING: &str = "bagjqcgzaday6dzalvmy5ady2m5a5legq5zrbsnlxfc2bfxej532ds7htpova";
+const STREAM_ID_STRING: &str = "kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s";
+const STREAM_ID_URL: &str =
+    "ceramic://kjzl6cwe1jw147dvq16zluojmraqvwdmbh61dx9e0c59i344lcrsgqfohexp60s";
+const STREAM_ID_WITH_COMMIT: &str =
+  "k1dpgaqe3i64kjqcp801r3sn7ysi5i0k7nxvs7j351s7kewfzr3l7mdxnj7szwo4kr9mn2qki5nnj0cv836ythy1t1gya9s25cn1nexst3jxi5o3h6qprfyju";
+const STREAM_ID_WITH_0_COMMIT: &str =
+    "k3y52l7qbv1frxwipl4hp7e6jlu4f6u8upm2xv0irmedfkm5cnutmezzi3u7mytj4";
+
+#[wasm_bindgen_test]
+fn stream_id() {
+    let stream_id = JsStreamId::from_string(STREAM_ID_URL).unwrap();
+
+    assert_eq!(stream_id.stream_type(), 0.0);
+    assert_eq!(stream_id.type_name(), "tile");
+    assert_eq!(stream_id.cid(), BASE_CID_STRING);
+    assert_eq!(stream_id.to_base36_string(), STREAM_ID_STRING);
+    assert_eq!(stream_id.to_url(), STREAM_ID_URL);
+    assert!(stream_id.equals(&JsStreamId::from_bytes(&stream_id.bytes()).unwrap()));
+    assert!(JsStreamId::from_string(STREAM_ID_WITH_COMMIT).is_err());
+}
+
+#[wasm_bindgen_test]
+fn commit_id() {
+    let stream_id = JsStreamId::from_string(STREAM_ID_STRING).unwrap();
+    let commit_id = stream_id.at_commit(COMMIT_CID_STRING).unwrap();
+
+    assert_eq!(commit_id.to_base36_string(), STREAM_ID_WITH_COMMIT);
+    assert_eq!(commit_id.commit(), COMMIT_CID_STRING);
+    assert!(commit_id.base_id().equals(&stream_id));
+    assert!(commit_id.equals(&JsCommitId::from_string(STREAM_ID_WITH_COMMIT).unwrap()));
+
+    let genesis = stream_id.at_commit("0").unwrap();
+    assert_eq!(genesis.to_base36_string(), STREAM_ID_WITH_0_COMMIT);
+    assert_eq!(genesis.commit(), BASE_CID_STRING);
+    assert!(genesis.equals(&commit_id.at_commit(BASE_CID_STRING).unwrap()));
+}
+
+#[wasm_bindgen_test]
+fn stream_ref() {
+    let stream_id: JsValue = JsStreamRef::from_string(STREAM_ID_STRING).unwrap();
+    let commit_id: JsValue = JsStreamRef::from_string(STREAM_ID_WITH_COMMIT).unwrap();
+
+    assert!(stream_id.is_object());
+    assert!(commit_id.is_object());
+    assert!(JsStreamRef::from_string("garbage").is_err());
+}